@@ -0,0 +1,28 @@
+use chrono::Weekday;
+use criterion::{criterion_group, criterion_main, Criterion};
+use text_calendar::{BasicMarker, Calendar, Calendars, YearCalendar};
+
+/// Ten years side by side, each a grid of twelve months
+fn ten_years() -> Calendars {
+    let years: Vec<Box<dyn Calendar>> = (2020..2030)
+        .map(|year| {
+            Box::new(YearCalendar::new(
+                year,
+                Weekday::Sun,
+                3,
+                BasicMarker::SquareBrackets,
+            )) as Box<dyn Calendar>
+        })
+        .collect();
+
+    Calendars::new(years, "2020-2029".to_string(), 2)
+}
+
+fn bench(c: &mut Criterion) {
+    let calendars = ten_years();
+
+    c.bench_function("10-year nested grid", |b| b.iter(|| calendars.to_string()));
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
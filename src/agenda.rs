@@ -0,0 +1,292 @@
+use std::{cmp::Reverse, fmt::Display, ops::RangeInclusive, rc::Rc};
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::{
+    utils::{display_width, pad},
+    BasicMarker, Calendar, Category, MarkStore, Region,
+};
+
+/// Marked day of an [`Agenda`]
+#[derive(Debug, Clone)]
+struct Entry {
+    date: NaiveDate,
+    /// Categories, highest precedence first
+    categories: Vec<Category>,
+    value: Option<f64>,
+    /// Annotations and holiday names
+    notes: Vec<String>,
+}
+
+impl Entry {
+    /// Decorated sample of the marker with the highest precedence
+    fn sample(&self) -> String {
+        self.categories
+            .first()
+            .map(|c| c.marker().decorate("  "))
+            .unwrap_or_default()
+    }
+
+    /// Category labels (and the value)
+    fn label(&self) -> String {
+        let mut label = self
+            .categories
+            .iter()
+            .map(|c| c.name())
+            .collect::<Vec<_>>()
+            .join(", ");
+        if let Some(value) = self.value {
+            label.push_str(&format!(" ({})", value));
+        }
+
+        label
+    }
+
+    fn is_marked(&self) -> bool {
+        self.categories.iter().any(|c| c.name() == Category::MARKED)
+    }
+}
+
+/// Chronological list of the marked days of a calendar, grouped by month
+///
+/// ```text
+/// June 2024
+/// Mon  3 [  ] marked
+/// Tue  4 !  ! on-call  Alice OOO
+/// Wed 19 *  * holiday  Juneteenth
+/// ```
+///
+/// The days are read from the calendar once; a [`MarkStore`] set with `set_mark_store` is
+/// read each time the agenda is drawn, so it follows the store like the calendars sharing it.
+#[derive(Debug, Clone)]
+pub struct Agenda {
+    entries: Vec<Entry>,
+    /// Dates shown from the store
+    range: Option<RangeInclusive<NaiveDate>>,
+    /// Category of the stored dates
+    marked: Category,
+    store: Option<Rc<dyn MarkStore>>,
+}
+
+impl Default for Agenda {
+    fn default() -> Self {
+        Self {
+            entries: vec![],
+            range: None,
+            marked: Category::new(Category::MARKED, BasicMarker::SquareBrackets),
+            store: None,
+        }
+    }
+}
+
+impl Agenda {
+    /// Agenda of the days with a category or an annotation in `calendar`
+    pub fn from_calendar<C: Calendar + ?Sized>(calendar: &C) -> Self {
+        let Some(range) = calendar.date_range() else {
+            return Self::default();
+        };
+        let marked = calendar
+            .categories()
+            .into_iter()
+            .find(|c| c.name() == Category::MARKED)
+            .unwrap_or_else(|| Self::default().marked);
+        let holidays = calendar.holidays();
+
+        let entries = range
+            .start()
+            .iter_days()
+            .take_while(|d| d <= range.end())
+            .filter_map(|date| {
+                let categories = calendar.categories_of(date);
+                let mut notes = calendar.annotations(date);
+                notes.extend(
+                    holidays
+                        .iter()
+                        .filter(|h| h.date == date)
+                        .map(|h| h.name.clone()),
+                );
+                if categories.is_empty() && notes.is_empty() {
+                    return None;
+                }
+
+                Some(Entry {
+                    date,
+                    categories,
+                    value: calendar.value(date),
+                    notes,
+                })
+            })
+            .collect();
+
+        Self {
+            entries,
+            range: Some(range),
+            marked,
+            store: None,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries().is_empty()
+    }
+
+    /// Days read from the calendar, with the dates of the store marked
+    fn entries(&self) -> Vec<Entry> {
+        let (Some(store), Some(range)) = (&self.store, &self.range) else {
+            return self.entries.clone();
+        };
+
+        let mut entries = self.entries.clone();
+        for date in store.marked_between(range.clone()) {
+            match entries.iter_mut().find(|e| e.date == date) {
+                Some(entry) if entry.is_marked() => {}
+                Some(entry) => {
+                    // the marked category comes first among equal priorities
+                    entry.categories.insert(0, self.marked.clone());
+                    entry.categories.sort_by_key(|c| Reverse(c.priority()));
+                }
+                None => entries.push(Entry {
+                    date,
+                    categories: vec![self.marked.clone()],
+                    value: None,
+                    notes: vec![],
+                }),
+            }
+        }
+        entries.sort_by_key(|e| e.date);
+
+        entries
+    }
+
+    fn lines(&self) -> Vec<String> {
+        let entries = self.entries();
+        let sample_width = entries
+            .iter()
+            .map(|e| display_width(&e.sample()))
+            .max()
+            .unwrap_or_default();
+        let label_width = entries
+            .iter()
+            .map(|e| display_width(&e.label()))
+            .max()
+            .unwrap_or_default();
+
+        let mut lines = vec![];
+        let mut month = None;
+
+        for entry in &entries {
+            let entry_month = (entry.date.year(), entry.date.month());
+            if month != Some(entry_month) {
+                if month.is_some() {
+                    lines.push(String::new());
+                }
+                lines.push(entry.date.format("%B %Y").to_string());
+                month = Some(entry_month);
+            }
+
+            let label = entry.label();
+            let mut line = format!(
+                "{} {} {}",
+                entry.date.format("%a %e"),
+                pad(&entry.sample(), sample_width),
+                label
+            );
+            if !entry.notes.is_empty() {
+                let space = label_width - display_width(&label);
+                line.push_str(&" ".repeat(space + 2));
+                line.push_str(&entry.notes.join("; "));
+            }
+
+            lines.push(line.trim_end().to_string());
+        }
+
+        lines
+    }
+}
+
+impl Calendar for Agenda {
+    fn mark(&mut self, _date: NaiveDate) {}
+    fn unmark(&mut self, _date: NaiveDate) {}
+
+    fn is_marked(&self, _date: NaiveDate) -> bool {
+        false
+    }
+
+    fn width(&self) -> usize {
+        self.lines()
+            .iter()
+            .map(|line| display_width(line))
+            .max()
+            .unwrap_or_default()
+    }
+
+    fn height(&self) -> usize {
+        self.lines().len()
+    }
+
+    fn draw(&self, region: &mut Region<'_>) {
+        for (row, line) in self.lines().iter().enumerate() {
+            region.write(row, line);
+        }
+    }
+
+    /// List the dates of `store` as marked, from now on
+    ///
+    /// The dates already in the store are taken to be marked by it, so they leave the agenda
+    /// when they are removed from the store.
+    fn set_mark_store(&mut self, store: Option<Rc<dyn MarkStore>>) {
+        if let Some(store) = &store {
+            for entry in &mut self.entries {
+                if store.contains(entry.date) {
+                    entry.categories.retain(|c| c.name() != Category::MARKED);
+                }
+            }
+            self.entries
+                .retain(|e| !e.categories.is_empty() || !e.notes.is_empty());
+        }
+        self.store = store;
+    }
+}
+
+impl Display for Agenda {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.render(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{n_date, BasicMarker, Category, Holiday, YearCalendar};
+
+    use super::*;
+
+    #[test]
+    fn test() {
+        let mut cal = YearCalendar::new(2024, chrono::Weekday::Sun, 4, BasicMarker::SquareBrackets);
+        cal.mark(n_date!(2024, 6, 3));
+        cal.mark_category(
+            n_date!(2024, 6, 4),
+            &Category::new("on-call", BasicMarker::Char('!')),
+        );
+        cal.annotate(n_date!(2024, 6, 4), "Alice OOO");
+        cal.mark_holiday(&Holiday::new(n_date!(2024, 7, 4), "Independence Day"));
+
+        let agenda = Agenda::from_calendar(&cal);
+        let output = agenda.to_string();
+        let lines: Vec<&str> = output.split('\n').collect();
+
+        assert_eq!(lines.len(), agenda.height());
+        assert!(lines.iter().all(|l| display_width(l) == agenda.width()));
+        assert_eq!(
+            lines.iter().map(|l| l.trim_end()).collect::<Vec<_>>(),
+            [
+                "June 2024",
+                "Mon  3 [  ] marked",
+                "Tue  4 !  ! on-call  Alice OOO",
+                "",
+                "July 2024",
+                "Thu  4 *  * holiday  Independence Day",
+            ]
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use crate::utils::{center, display_width, pad, truncate};
+
+/// Horizontal placement of a text in a wider space
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Alignment {
+    Left,
+    #[default]
+    Center,
+    Right,
+}
+
+impl Alignment {
+    /// Place `s` in `width` columns, cutting it if it is wider
+    pub(crate) fn align(&self, s: &str, width: usize) -> String {
+        match self {
+            Alignment::Left => pad(truncate(s, width), width),
+            Alignment::Center => center(s, width),
+            Alignment::Right => {
+                let s = truncate(s, width);
+                format!("{}{}", " ".repeat(width - display_width(s)), s)
+            }
+        }
+    }
+
+    /// Columns left of a block of `width` columns placed in `space` columns
+    pub(crate) fn offset(&self, width: usize, space: usize) -> usize {
+        let free = space.saturating_sub(width);
+
+        match self {
+            Alignment::Left => 0,
+            Alignment::Center => free / 2,
+            Alignment::Right => free,
+        }
+    }
+}
+
+/// Vertical placement of a calendar in a taller space
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VerticalAlignment {
+    #[default]
+    Top,
+    Center,
+    Bottom,
+}
+
+impl VerticalAlignment {
+    /// Lines above a block of `height` lines placed in `space` lines
+    pub(crate) fn offset(&self, height: usize, space: usize) -> usize {
+        let free = space.saturating_sub(height);
+
+        match self {
+            VerticalAlignment::Top => 0,
+            VerticalAlignment::Center => free / 2,
+            VerticalAlignment::Bottom => free,
+        }
+    }
+}
@@ -1,5 +1,15 @@
-use chrono::NaiveDate;
-use std::fmt::{Debug, Display};
+use chrono::{Datelike, NaiveDate};
+use std::{
+    fmt::{self, Debug, Display},
+    io,
+    ops::RangeInclusive,
+    rc::Rc,
+};
+
+use crate::{
+    canvas::IoWriter, Canvas, Category, Holiday, HolidayProvider, MarkStore, Marker,
+    MarkerCombination, RangeMarker, Region, TodayHighlight, WeekendStyle,
+};
 
 pub trait Calendar: Display {
     fn mark(&mut self, date: NaiveDate);
@@ -8,10 +18,140 @@ pub trait Calendar: Display {
 
     fn width(&self) -> usize;
     fn height(&self) -> usize;
+
+    /// Draw the calendar into `region`, one call per line (or part of a line)
+    ///
+    /// There is no default: `Display` of the calendars is a wrapper of `render`, which
+    /// draws, so drawing cannot go through `to_string()`.
+    fn draw(&self, region: &mut Region<'_>);
+
+    /// Write the calendar into `w` line by line, without a trailing newline
+    ///
+    /// `Display` is a wrapper of this. By default, the calendar is drawn into a [`Canvas`]
+    /// and then written; containers write one band of children at a time.
+    fn render(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        let mut canvas = Canvas::new();
+        self.draw(&mut canvas.region());
+
+        canvas.write_to(w, self.width(), self.height())
+    }
+
+    /// Same as `render`, into a file, a locked stdout, etc.
+    fn render_io(&self, w: &mut dyn io::Write) -> io::Result<()> {
+        let mut writer = IoWriter::new(w);
+
+        self.render(&mut writer).map_err(|_| writer.into_error())
+    }
+
+    /// Mark every date of the range, drawn as one connected range where supported
+    fn mark_range(&mut self, range: RangeInclusive<NaiveDate>) {
+        for date in range.start().iter_days().take_while(|d| d <= range.end()) {
+            self.mark(date);
+        }
+    }
+
+    /// Unmark every date of the range
+    fn unmark_range(&mut self, range: RangeInclusive<NaiveDate>) {
+        for date in range.start().iter_days().take_while(|d| d <= range.end()) {
+            self.unmark(date);
+        }
+    }
+
+    /// Marker for ranges marked by `mark_range`
+    fn set_range_marker(&mut self, _marker: Rc<dyn RangeMarker>) {}
+
+    /// First and last dates shown in the calendar
+    fn date_range(&self) -> Option<RangeInclusive<NaiveDate>> {
+        None
+    }
+
+    /// Mark the date with a category
+    ///
+    /// A category named [`Category::MARKED`] or [`Category::HOLIDAY`] marks the date with
+    /// the built-in one, whose marker and priority stay as they are.
+    fn mark_category(&mut self, _date: NaiveDate, _category: &Category) {}
+
+    /// Remove the category named `name` from the date
+    fn unmark_category(&mut self, _date: NaiveDate, _name: &str) {}
+
+    /// Categories of the date, highest precedence first
+    ///
+    /// `mark` and `mark_holiday` appear as [`Category::MARKED`] and [`Category::HOLIDAY`].
+    fn categories_of(&self, _date: NaiveDate) -> Vec<Category> {
+        vec![]
+    }
+
+    /// Categories used in the calendar, highest precedence first
+    fn categories(&self) -> Vec<Category> {
+        vec![]
+    }
+
+    /// How days with several categories are decorated
+    fn set_combination(&mut self, _combination: MarkerCombination) {}
+
+    /// Attach a note (e.g. "v2.3 release") to the date
+    fn annotate(&mut self, _date: NaiveDate, _text: &str) {}
+
+    /// Remove the notes of the date
+    fn remove_annotations(&mut self, _date: NaiveDate) {}
+
+    /// Notes of the date, in the order they were added
+    fn annotations(&self, _date: NaiveDate) -> Vec<String> {
+        vec![]
+    }
+
+    /// Mark a named holiday (drawn with the holiday marker, separately from `mark`)
+    fn mark_holiday(&mut self, _holiday: &Holiday) {}
+
+    /// Holidays marked in the calendar, in date order
+    fn holidays(&self) -> Vec<Holiday> {
+        vec![]
+    }
+
+    /// Marker used for holidays
+    fn set_holiday_marker(&mut self, _marker: Rc<dyn Marker>) {}
+
+    /// Mark the date with a value (e.g. hours worked), passed to value-aware markers
+    /// such as [`ScaleMarker`](crate::ScaleMarker)
+    ///
+    /// [`MonthCalendar`](crate::MonthCalendar) also marks the date, whose marker draws the
+    /// value, and `unmark` removes the value again.
+    fn set_value(&mut self, _date: NaiveDate, _value: f64) {}
+
+    /// Value of the date, if any
+    fn value(&self, _date: NaiveDate) -> Option<f64> {
+        None
+    }
+
+    /// Highlight the current date (`None` to turn it off)
+    fn set_today_highlight(&mut self, _highlight: Option<TodayHighlight>) {}
+
+    /// Style the weekend columns (`None` to turn it off)
+    fn set_weekend_style(&mut self, _style: Option<WeekendStyle>) {}
+
+    /// Also show the dates of `store` as marked (`None` to stop sharing)
+    ///
+    /// The store is only read; `mark` and `unmark` still change the calendar's own marks.
+    fn set_mark_store(&mut self, _store: Option<Rc<dyn MarkStore>>) {}
+
+    /// Mark every holiday of `provider` shown in the calendar
+    fn apply_holidays(&mut self, provider: &dyn HolidayProvider) {
+        let Some(range) = self.date_range() else {
+            return;
+        };
+
+        for year in range.start().year()..=range.end().year() {
+            for holiday in provider.holidays(year) {
+                if range.contains(&holiday.date) {
+                    self.mark_holiday(&holiday);
+                }
+            }
+        }
+    }
 }
 
 impl Debug for dyn Calendar {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self)
     }
-}
\ No newline at end of file
+}
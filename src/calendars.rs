@@ -1,151 +1,493 @@
-use std::fmt::{Display, Write};
-
-use chrono::NaiveDate;
-
-use crate::{Calendar, CalendarCollection, EmptyCalendar};
+use std::{
+    cmp::Reverse,
+    fmt::{Display, Write},
+    io,
+    ops::Range,
+};
+
+use crate::{
+    canvas::{write_spaces, IoWriter},
+    forward::forward_calendar_to_all,
+    Alignment, Calendar, CalendarCollection, Canvas, Category, Error, Region, VerticalAlignment,
+};
 
 /// multiple calendars
+///
+/// Calendars are laid out in a grid of `cols` columns. Each column is as wide as its widest
+/// calendar (see [`Sizing`]) and each row as tall as its tallest one; smaller calendars are
+/// placed in their cell by `set_alignment`.
 #[derive(Debug)]
 pub struct Calendars {
     calendars: Vec<Box<dyn Calendar>>,
     title: String,
     cols: usize,
-    width: usize,
-    padding: usize,
+    /// `None` for the default gap, which follows the column widths
+    column_gap: Option<usize>,
+    row_gap: usize,
+    margin: usize,
+    alignment: Alignment,
+    vertical_alignment: VerticalAlignment,
+    title_alignment: Alignment,
+    sizing: Sizing,
+}
+
+/// How the cells of [`Calendars`] are sized when calendars have different widths
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Sizing {
+    /// Every column is as wide as its widest calendar, so columns line up across rows
+    #[default]
+    PerColumn,
+
+    /// Every row packs its calendars at their own widths, placed in the grid by the alignment
+    PerRow,
 }
 
 impl CalendarCollection for Calendars {}
 
 impl Calendars {
-    pub fn new(mut calendars: Vec<Box<dyn Calendar>>, title: String, cols: usize) -> Self {
-        let last_padding = cols - (calendars.len() % cols);
+    pub fn new(calendars: Vec<Box<dyn Calendar>>, title: String, cols: usize) -> Self {
+        let cols = cols.max(1);
 
-        let last_height = calendars[calendars.len() - (cols - last_padding)..]
-            .iter()
-            .map(|c| c.height())
-            .max()
-            .unwrap_or_default();
+        let mut calendars = Self {
+            calendars,
+            title,
+            cols: 0,
+            column_gap: None,
+            row_gap: 1,
+            margin: 0,
+            alignment: Alignment::Left,
+            vertical_alignment: VerticalAlignment::Top,
+            title_alignment: Alignment::Center,
+            sizing: Sizing::PerColumn,
+        };
+        calendars.set_cols(cols);
+
+        calendars
+    }
 
-        if last_padding != cols {
-            let empty_cal_width = calendars.last().unwrap().width();
+    /// Grid with as many columns as fit in `width` columns (e.g. the width of the terminal)
+    ///
+    /// It is an error if the widest calendar alone does not fit.
+    pub fn fit(
+        calendars: Vec<Box<dyn Calendar>>,
+        title: String,
+        width: usize,
+    ) -> Result<Self, Error> {
+        let mut fitted = Self::new(calendars, title, 1);
+        let cols: Vec<usize> = (1..=fitted.calendars.len().max(1)).rev().collect();
+
+        fitted.fit_cols(&cols, width)?;
+        Ok(fitted)
+    }
 
-            calendars.extend((0..last_padding).map(|_| {
-                Box::new(EmptyCalendar::new(last_height, empty_cal_width)) as Box<dyn Calendar>
-            }));
+    /// Use the first of `cols` that fits in `width` columns
+    pub(crate) fn fit_cols(&mut self, cols: &[usize], width: usize) -> Result<(), Error> {
+        for cols in cols {
+            self.set_cols(*cols);
+            if self.width() <= width {
+                return Ok(());
+            }
         }
 
-        let no_padding_width = calendars
-            .windows(cols)
-            .step_by(cols)
-            .map(|w| w.iter().map(|c| c.width()).sum::<usize>())
-            .max()
-            .unwrap_or_default();
+        Err(Error::TooNarrow {
+            available: width,
+            required: self.width(),
+        })
+    }
 
-        let padding = no_padding_width / cols / 7;
+    fn set_cols(&mut self, cols: usize) {
+        self.cols = cols.max(1);
+    }
 
-        let width = no_padding_width + padding * (cols - 1);
+    /// Columns between calendars side by side (default: a seventh of the average column width)
+    pub fn set_column_gap(&mut self, gap: usize) {
+        self.column_gap = Some(gap);
+    }
 
-        Self {
-            calendars,
-            title,
-            cols,
-            width,
-            padding,
+    fn column_gap(&self) -> usize {
+        self.column_gap.unwrap_or_else(|| {
+            let widths = self.column_widths();
+            widths.iter().sum::<usize>() / widths.len().max(1) / 7
+        })
+    }
+
+    /// Blank lines between rows of calendars (default: 1)
+    pub fn set_row_gap(&mut self, gap: usize) {
+        self.row_gap = gap;
+    }
+
+    /// Blank columns and lines around the whole grid, title included (default: 0)
+    pub fn set_margin(&mut self, margin: usize) {
+        self.margin = margin;
+    }
+
+    /// Placement of calendars smaller than their cell (default: top left)
+    pub fn set_alignment(&mut self, alignment: Alignment, vertical_alignment: VerticalAlignment) {
+        self.alignment = alignment;
+        self.vertical_alignment = vertical_alignment;
+    }
+
+    /// Placement of the title (default: centered)
+    pub fn set_title_alignment(&mut self, alignment: Alignment) {
+        self.title_alignment = alignment;
+    }
+
+    pub fn set_sizing(&mut self, sizing: Sizing) {
+        self.sizing = sizing;
+    }
+
+    /// Rows of calendars
+    fn bands(&self) -> std::slice::Chunks<'_, Box<dyn Calendar>> {
+        self.calendars.chunks(self.cols)
+    }
+
+    /// Width of the widest calendar of each column
+    fn column_widths(&self) -> Vec<usize> {
+        let mut widths = vec![0; self.cols.min(self.calendars.len())];
+        for (i, calendar) in self.calendars.iter().enumerate() {
+            widths[i % self.cols] = widths[i % self.cols].max(calendar.width());
         }
+
+        widths
+    }
+
+    /// Widths of the cells of a row
+    fn cell_widths(&self, band: &[Box<dyn Calendar>], column_widths: &[usize]) -> Vec<usize> {
+        match self.sizing {
+            Sizing::PerColumn => column_widths[..band.len()].to_vec(),
+            Sizing::PerRow => band.iter().map(|c| c.width()).collect(),
+        }
+    }
+
+    /// Width of cells and gaps
+    fn row_width(&self, widths: &[usize]) -> usize {
+        widths.iter().sum::<usize>() + self.column_gap() * widths.len().saturating_sub(1)
     }
 
     fn height_list(&self) -> impl Iterator<Item = usize> + '_ {
-        self.calendars
-            .windows(self.cols)
-            .step_by(self.cols)
-            .map(|w| w.iter().map(|c| c.height()).max().unwrap_or_default())
+        self.bands()
+            .map(|band| band.iter().map(|c| c.height()).max().unwrap_or_default())
     }
-}
 
-impl Calendar for Calendars {
-    fn is_marked(&self, date: NaiveDate) -> bool {
-        self.calendars.iter().any(|c| c.is_marked(date))
+    /// The whole grid
+    fn whole(&self) -> Part {
+        Part {
+            bands: 0..self.calendars.len().div_ceil(self.cols),
+            columns: 0..self.cols.min(self.calendars.len()),
+        }
     }
 
-    fn mark(&mut self, date: NaiveDate) {
-        self.calendars.iter_mut().for_each(|c| c.mark(date));
+    /// Calendars of the part, row by row, with the height of their whole row
+    fn part_bands<'a>(
+        &'a self,
+        part: &'a Part,
+    ) -> impl Iterator<Item = (&'a [Box<dyn Calendar>], usize)> + 'a {
+        self.bands()
+            .zip(self.height_list())
+            .skip(part.bands.start)
+            .take(part.bands.len())
+            .map(|(band, height)| {
+                let start = part.columns.start.min(band.len());
+                let end = part.columns.end.min(band.len());
+                (&band[start..end], height)
+            })
     }
 
-    fn unmark(&mut self, date: NaiveDate) {
-        self.calendars.iter_mut().for_each(|c| c.unmark(date));
+    /// Width of the part, without the margin
+    fn inner_width(&self, part: &Part) -> usize {
+        let column_widths = &self.column_widths()[part.columns.clone()];
+
+        match self.sizing {
+            Sizing::PerColumn => self.row_width(column_widths),
+            Sizing::PerRow => self
+                .part_bands(part)
+                .map(|(band, _)| self.row_width(&self.cell_widths(band, column_widths)))
+                .max()
+                .unwrap_or_default(),
+        }
     }
 
-    fn width(&self) -> usize {
-        self.width
+    fn part_width(&self, part: &Part) -> usize {
+        self.inner_width(part) + self.margin * 2
     }
 
-    fn height(&self) -> usize {
-        1 + self.height_list().sum::<usize>() + (self.calendars.len() / self.cols - 1)
+    fn part_height(&self, part: &Part) -> usize {
+        self.margin * 2
+            + 1
+            + self
+                .part_bands(part)
+                .map(|(_, height)| height)
+                .sum::<usize>()
+            + self.row_gap * part.bands.len().saturating_sub(1)
     }
-}
 
-impl Display for Calendars {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let max_line_width = self.width();
-
-        writeln!(f, "{: ^width$}", self.title, width = max_line_width)?;
-
-        let height_list: Vec<usize> = self.height_list().collect();
-
-        let windows = self
-            .calendars
-            .windows(self.cols)
-            .step_by(self.cols)
-            .enumerate();
-
-        for (line_count, calendars) in windows {
-            let lines_list: Vec<Vec<String>> = calendars
-                .iter()
-                .map(|c| {
-                    c.to_string()
-                        .split('\n')
-                        .map(|s| s.to_string())
-                        .collect::<Vec<String>>()
-                })
-                .collect();
-
-            for i in 0..height_list[line_count] {
-                let mut line = String::new();
-
-                for cal in &lines_list {
-                    if let Some(cal_line) = cal.get(i) {
-                        write!(&mut line, "{}{}", cal_line, " ".repeat(self.padding))?;
-                    } else {
-                        write!(&mut line, "{}", " ".repeat(cal[0].len() + self.padding))?;
-                    }
-                }
+    /// Draw a row of calendars side by side, `height` lines tall, in a part `inner_width` wide
+    fn draw_band(
+        &self,
+        calendars: &[Box<dyn Calendar>],
+        column_widths: &[usize],
+        height: usize,
+        inner_width: usize,
+        region: &mut Region<'_>,
+    ) {
+        let widths = self.cell_widths(calendars, column_widths);
+        let mut x = self.margin;
+        if self.sizing == Sizing::PerRow {
+            x += self.alignment.offset(self.row_width(&widths), inner_width);
+        }
 
-                for _ in 0..self.padding {
-                    line.pop();
-                }
+        let gap = self.column_gap();
+        for (calendar, width) in calendars.iter().zip(&widths) {
+            let dx = self.alignment.offset(calendar.width(), *width);
+            let dy = self.vertical_alignment.offset(calendar.height(), height);
+            calendar.draw(&mut region.region(x + dx, dy).with_width(calendar.width()));
+            x += width + gap;
+        }
+    }
 
-                write!(f, "{}", line)?;
+    fn fmt_title(&self, title: &str, inner_width: usize) -> String {
+        format!(
+            "{0}{1}{0}",
+            " ".repeat(self.margin),
+            self.title_alignment.align(title, inner_width)
+        )
+    }
 
-                if line_count < self.calendars.len() / self.cols - 1
-                    || i != height_list[line_count] - 1
-                {
-                    writeln!(f)?;
-                }
+    fn draw_part(&self, title: &str, part: &Part, region: &mut Region<'_>) {
+        let column_widths = &self.column_widths()[part.columns.clone()];
+        let inner_width = self.inner_width(part);
+        region.write(self.margin, &self.fmt_title(title, inner_width));
+
+        let mut y = self.margin + 1;
+
+        for (i, (calendars, height)) in self.part_bands(part).enumerate() {
+            // カレンダーの間
+            if i != 0 {
+                y += self.row_gap;
             }
 
+            let mut band = region.region(0, y);
+            self.draw_band(calendars, column_widths, height, inner_width, &mut band);
+            y += height;
+        }
+    }
+
+    /// Write the title, then each row of calendars as soon as it is drawn
+    fn render_part(&self, title: &str, part: &Part, w: &mut dyn Write) -> std::fmt::Result {
+        let column_widths = &self.column_widths()[part.columns.clone()];
+        let inner_width = self.inner_width(part);
+        let width = inner_width + self.margin * 2;
+
+        for _ in 0..self.margin {
+            write_spaces(w, width)?;
+            w.write_char('\n')?;
+        }
+        w.write_str(&self.fmt_title(title, inner_width))?;
+
+        for (i, (calendars, height)) in self.part_bands(part).enumerate() {
             // カレンダーの間
-            if line_count < self.calendars.len() / self.cols - 1 {
-                writeln!(f, "{}", " ".repeat(max_line_width))?;
+            if i != 0 {
+                for _ in 0..self.row_gap {
+                    w.write_char('\n')?;
+                    write_spaces(w, width)?;
+                }
+            }
+
+            let mut canvas = Canvas::new();
+            let mut band = canvas.region();
+            self.draw_band(calendars, column_widths, height, inner_width, &mut band);
+            if height > 0 {
+                w.write_char('\n')?;
+                canvas.write_to(w, width, height)?;
             }
         }
 
+        for _ in 0..self.margin {
+            w.write_char('\n')?;
+            write_spaces(w, width)?;
+        }
+
         Ok(())
     }
+
+    /// Split the grid into pages of at most `lines` lines and `columns` columns
+    ///
+    /// Pages hold whole rows and columns of calendars, so no calendar is cut. When there
+    /// are several pages, their titles are numbered (`2024 (1/2)`, or `(1/2)` without a
+    /// title).
+    ///
+    /// Pages are text, like the rest of this crate: there are no HTML or SVG outputs to
+    /// split.
+    pub fn pages(&self, lines: usize, columns: usize) -> Result<Vec<Page<'_>>, Error> {
+        let whole = self.whole();
+
+        let mut column_groups = vec![];
+        let mut start = whole.columns.start;
+        while start < whole.columns.end || column_groups.is_empty() {
+            let part = |end| Part {
+                bands: whole.bands.clone(),
+                columns: start..end,
+            };
+
+            let mut end = (start + 1).min(whole.columns.end);
+            if self.part_width(&part(end)) > columns {
+                return Err(Error::TooNarrow {
+                    available: columns,
+                    required: self.part_width(&part(end)),
+                });
+            }
+            while end < whole.columns.end && self.part_width(&part(end + 1)) <= columns {
+                end += 1;
+            }
+
+            column_groups.push(start..end);
+            start = end;
+        }
+
+        let mut band_groups = vec![];
+        let mut start = whole.bands.start;
+        while start < whole.bands.end || band_groups.is_empty() {
+            let part = |end| Part {
+                bands: start..end,
+                columns: whole.columns.clone(),
+            };
+
+            let mut end = (start + 1).min(whole.bands.end);
+            if self.part_height(&part(end)) > lines {
+                return Err(Error::TooShort {
+                    available: lines,
+                    required: self.part_height(&part(end)),
+                });
+            }
+            while end < whole.bands.end && self.part_height(&part(end + 1)) <= lines {
+                end += 1;
+            }
+
+            band_groups.push(start..end);
+            start = end;
+        }
+
+        let count = column_groups.len() * band_groups.len();
+        let mut pages = vec![];
+
+        for bands in &band_groups {
+            for columns in &column_groups {
+                let number = format!("({}/{})", pages.len() + 1, count);
+                let title = if count == 1 {
+                    self.title.clone()
+                } else if self.title.is_empty() {
+                    number
+                } else {
+                    format!("{} {}", self.title, number)
+                };
+
+                pages.push(Page {
+                    calendars: self,
+                    part: Part {
+                        bands: bands.clone(),
+                        columns: columns.clone(),
+                    },
+                    title,
+                });
+            }
+        }
+
+        Ok(pages)
+    }
+}
+
+/// Rows and columns of calendars in a part of the grid
+#[derive(Debug, Clone)]
+struct Part {
+    bands: Range<usize>,
+    columns: Range<usize>,
+}
+
+/// Part of [`Calendars`] fitting on a page, made by [`Calendars::pages`]
+#[derive(Debug)]
+pub struct Page<'a> {
+    calendars: &'a Calendars,
+    part: Part,
+    title: String,
+}
+
+impl Page<'_> {
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn width(&self) -> usize {
+        self.calendars.part_width(&self.part)
+    }
+
+    pub fn height(&self) -> usize {
+        self.calendars.part_height(&self.part)
+    }
+
+    /// Same as [`Calendar::render`]
+    pub fn render(&self, w: &mut dyn Write) -> std::fmt::Result {
+        self.calendars.render_part(&self.title, &self.part, w)
+    }
+
+    /// Same as [`Calendar::render_io`]
+    pub fn render_io(&self, w: &mut dyn io::Write) -> io::Result<()> {
+        let mut writer = IoWriter::new(w);
+
+        self.render(&mut writer).map_err(|_| writer.into_error())
+    }
+}
+
+impl Display for Page<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.render(f)
+    }
+}
+
+impl Calendar for Calendars {
+    fn width(&self) -> usize {
+        self.part_width(&self.whole())
+    }
+
+    fn height(&self) -> usize {
+        self.part_height(&self.whole())
+    }
+
+    fn draw(&self, region: &mut Region<'_>) {
+        self.draw_part(&self.title, &self.whole(), region)
+    }
+
+    fn render(&self, w: &mut dyn Write) -> std::fmt::Result {
+        self.render_part(&self.title, &self.whole(), w)
+    }
+
+    forward_calendar_to_all!(calendars);
+}
+
+/// Concatenate category lists, keeping the first of each name
+pub(crate) fn merge_categories(lists: impl Iterator<Item = Vec<Category>>) -> Vec<Category> {
+    let mut merged: Vec<Category> = vec![];
+
+    for category in lists.flatten() {
+        if !merged.contains(&category) {
+            merged.push(category);
+        }
+    }
+    merged.sort_by_key(|c| Reverse(c.priority()));
+
+    merged
+}
+
+impl Display for Calendars {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.render(f)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{n_date, MonthCalendar};
+    use chrono::Weekday;
+
+    use crate::{n_date, Alignment, BasicMarker, MonthCalendar, VerticalAlignment, YearCalendar};
 
     use super::*;
 
@@ -167,4 +509,194 @@ mod tests {
         let cals = Calendars::new(c2024, "2024".to_string(), 5);
         println!("{}", cals);
     }
+
+    #[test]
+    fn test_render() {
+        let years: Vec<Box<dyn Calendar>> = vec![
+            Box::new(YearCalendar::new(
+                2024,
+                Weekday::Sun,
+                3,
+                BasicMarker::SquareBrackets,
+            )),
+            Box::new(YearCalendar::new(
+                2025,
+                Weekday::Sun,
+                3,
+                BasicMarker::SquareBrackets,
+            )),
+            Box::new(MonthCalendar::from_ym(2026, 1).unwrap()),
+        ];
+        let cals = Calendars::new(years, "2024-2026".to_string(), 2);
+
+        // streamed by band, same as drawing the whole grid at once
+        let mut canvas = Canvas::new();
+        cals.draw(&mut canvas.region());
+        let mut drawn = String::new();
+        canvas
+            .write_to(&mut drawn, cals.width(), cals.height())
+            .unwrap();
+        assert_eq!(cals.to_string(), drawn);
+
+        let mut bytes: Vec<u8> = vec![];
+        cals.render_io(&mut bytes).unwrap();
+        assert_eq!(String::from_utf8(bytes).unwrap(), drawn);
+    }
+
+    #[test]
+    fn test_layout() {
+        let june = || MonthCalendar::new(2024, 6, Weekday::Sun, 3, BasicMarker::None).unwrap();
+        let feb = || MonthCalendar::from_ym(2026, 2).unwrap();
+
+        let mut side = Calendars::new(
+            vec![Box::new(june()), Box::new(feb())],
+            "Mixed".to_string(),
+            2,
+        );
+        side.set_alignment(Alignment::Left, VerticalAlignment::Bottom);
+        side.set_margin(1);
+        side.set_column_gap(2);
+        side.set_title_alignment(Alignment::Left);
+
+        let output = side.to_string();
+        let lines: Vec<&str> = output.split('\n').collect();
+        assert_eq!(
+            (side.width(), side.height()),
+            (1 + 21 + 2 + 28 + 1, 1 + 1 + 8 + 1)
+        );
+        assert_eq!(lines.len(), side.height());
+        assert!(lines.iter().all(|l| l.len() == side.width()));
+        assert_eq!(lines[0].trim(), "");
+        assert_eq!(lines[1].trim_end(), " Mixed");
+        // February is 2 lines shorter than June
+        assert_eq!(lines[4].trim(), "1             February");
+        assert_eq!(lines[10].trim(), "");
+
+        // a gap set by the user is kept when the columns change
+        side.fit_cols(&[2], 60).unwrap();
+        assert_eq!(side.width(), 1 + 21 + 2 + 28 + 1);
+
+        let mut stacked =
+            Calendars::new(vec![Box::new(june()), Box::new(feb())], "".to_string(), 1);
+        stacked.set_alignment(Alignment::Center, VerticalAlignment::Top);
+        stacked.set_row_gap(0);
+
+        let output = stacked.to_string();
+        let lines: Vec<&str> = output.split('\n').collect();
+        assert_eq!(stacked.height(), 1 + 8 + 6);
+        assert_eq!(lines[2], format!("   {}    ", "Su Mo Tu We Th Fr Sa "));
+        assert_eq!(lines[9].trim(), "February");
+
+        let mut bytes: Vec<u8> = vec![];
+        side.render_io(&mut bytes).unwrap();
+        let mut canvas = Canvas::new();
+        side.draw(&mut canvas.region());
+        let mut drawn = String::new();
+        canvas
+            .write_to(&mut drawn, side.width(), side.height())
+            .unwrap();
+        assert_eq!(String::from_utf8(bytes).unwrap(), drawn);
+    }
+
+    #[test]
+    fn test_mixed_widths() {
+        let month = |month, day_width| {
+            Box::new(
+                MonthCalendar::new(2024, month, Weekday::Sun, day_width, BasicMarker::None)
+                    .unwrap(),
+            ) as Box<dyn Calendar>
+        };
+        let q1 = Calendars::new(vec![month(1, 2), month(2, 2)], "Q1".to_string(), 2);
+        let mut cals = Calendars::new(
+            vec![
+                month(3, 3),
+                Box::new(q1),
+                month(4, 4),
+                month(5, 3),
+                month(6, 4),
+            ],
+            "Mixed".to_string(),
+            2,
+        );
+
+        // columns of 28 (April, June) and 30 (Q1) with a gap of 4
+        let output = cals.to_string();
+        let lines: Vec<&str> = output.split('\n').collect();
+        assert_eq!(cals.width(), 28 + 4 + 30);
+        assert_eq!(lines.len(), cals.height());
+        assert!(lines.iter().all(|l| l.len() == cals.width()));
+        assert!(lines[2][32..].starts_with("   January"));
+        assert!(lines[11][32..].starts_with("Su Mo Tu"));
+        assert!(lines[19].starts_with(" Su  Mo "));
+
+        cals.set_sizing(Sizing::PerRow);
+        cals.set_alignment(Alignment::Center, VerticalAlignment::Top);
+
+        let output = cals.to_string();
+        let lines: Vec<&str> = output.split('\n').collect();
+        assert_eq!(cals.width(), 21 + 4 + 30);
+        assert_eq!(lines.len(), cals.height());
+        assert!(lines.iter().all(|l| l.len() == cals.width()));
+        assert!(lines[2][25..].starts_with("   January"));
+        assert_eq!(lines[18].find("June"), Some((55 - 28) / 2 + 12));
+    }
+
+    #[test]
+    fn test_pages() {
+        let months: Vec<Box<dyn Calendar>> = (0..24)
+            .map(|i| {
+                Box::new(MonthCalendar::from_ym(2024 + i / 12, i as u32 % 12 + 1).unwrap())
+                    as Box<dyn Calendar>
+            })
+            .collect();
+        let cals = Calendars::new(months, "2024-2025".to_string(), 3);
+
+        // 4 rows of months per page
+        let pages = cals.pages(40, 92).unwrap();
+        assert_eq!(pages.len(), 2);
+        for page in &pages {
+            let output = page.to_string();
+            let lines: Vec<&str> = output.split('\n').collect();
+            assert!(page.height() <= 40);
+            assert_eq!(lines.len(), page.height());
+            assert!(lines.iter().all(|l| l.len() == 92));
+            assert_eq!(lines[0].trim(), page.title());
+            assert_eq!(output.matches("January").count(), 1);
+        }
+        assert_eq!(pages[1].title(), "2024-2025 (2/2)");
+
+        // and 2 columns, then the last one
+        let pages = cals.pages(40, 60).unwrap();
+        assert_eq!(pages.len(), 4);
+        assert_eq!(
+            pages.iter().map(|p| p.width()).collect::<Vec<_>>(),
+            [60, 28, 60, 28]
+        );
+        assert!(pages[1].to_string().contains("March"));
+        assert!(!pages[1].to_string().contains("January"));
+
+        let mut bytes: Vec<u8> = vec![];
+        pages[3].render_io(&mut bytes).unwrap();
+        assert_eq!(String::from_utf8(bytes).unwrap(), pages[3].to_string());
+
+        assert_eq!(cals.pages(1000, 1000).unwrap()[0].title(), "2024-2025");
+        assert!(matches!(cals.pages(5, 92), Err(Error::TooShort { .. })));
+        assert_eq!(
+            cals.pages(40, 20).unwrap_err(),
+            Error::TooNarrow {
+                available: 20,
+                required: 28
+            }
+        );
+
+        let months: Vec<Box<dyn Calendar>> = (1..=2)
+            .map(|m| Box::new(MonthCalendar::from_ym(2024, m).unwrap()) as Box<dyn Calendar>)
+            .collect();
+        let untitled = Calendars::new(months, String::new(), 1);
+        let pages = untitled.pages(12, 28).unwrap();
+        assert_eq!(
+            pages.iter().map(|p| p.title()).collect::<Vec<_>>(),
+            ["(1/2)", "(2/2)"]
+        );
+    }
 }
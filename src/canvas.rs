@@ -0,0 +1,197 @@
+use std::{borrow::Cow, fmt::Write, io};
+
+use crate::utils::{display_width, truncate};
+
+/// Lines of text being composed, with their widths in columns
+///
+/// Calendars draw into a [`Region`] of the canvas, and containers such as
+/// [`Calendars`](crate::Calendars) give each child its own region, so a whole grid
+/// is composed in one buffer and serialized once.
+#[derive(Debug, Default)]
+pub struct Canvas {
+    lines: Vec<String>,
+    widths: Vec<usize>,
+}
+
+impl Canvas {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Region covering the whole canvas
+    pub fn region(&mut self) -> Region<'_> {
+        Region {
+            canvas: self,
+            x: 0,
+            y: 0,
+            width: None,
+        }
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.lines.iter().map(|line| line.as_str())
+    }
+
+    /// Write at least `height` lines, padded to `width` columns and separated by `'\n'`
+    pub fn write_to(
+        &self,
+        w: &mut (impl Write + ?Sized),
+        width: usize,
+        height: usize,
+    ) -> std::fmt::Result {
+        for i in 0..self.lines.len().max(height) {
+            if i != 0 {
+                w.write_char('\n')?;
+            }
+
+            let (line, line_width) = match self.lines.get(i) {
+                Some(line) => (line.as_str(), self.widths[i]),
+                None => ("", 0),
+            };
+            w.write_str(line)?;
+            write_spaces(w, width.saturating_sub(line_width))?;
+        }
+
+        Ok(())
+    }
+
+    /// Append `text` to line `y`, starting at column `x` or later and cut at column `end`
+    fn put(&mut self, x: usize, y: usize, text: &str, end: Option<usize>) {
+        if self.lines.len() <= y {
+            self.lines.resize_with(y + 1, String::new);
+            self.widths.resize(y + 1, 0);
+        }
+
+        let line = &mut self.lines[y];
+        let width = &mut self.widths[y];
+        for _ in *width..x {
+            line.push(' ');
+        }
+        let start = (*width).max(x);
+        let text = match end {
+            Some(end) => clip(text, end.saturating_sub(start)),
+            None => Cow::Borrowed(text),
+        };
+        line.push_str(&text);
+        *width = start + display_width(&text);
+    }
+}
+
+/// `text` cut to `width` columns, with the escape codes reset if it was cut
+fn clip(text: &str, width: usize) -> Cow<'_, str> {
+    let cut = truncate(text, width);
+
+    if cut.len() < text.len() && text.contains('\x1b') {
+        Cow::Owned(format!("{}\x1b[0m", cut))
+    } else {
+        Cow::Borrowed(cut)
+    }
+}
+
+pub(crate) fn write_spaces(w: &mut (impl Write + ?Sized), mut n: usize) -> std::fmt::Result {
+    const SPACES: &str = "                                ";
+
+    while n > 0 {
+        let len = n.min(SPACES.len());
+        w.write_str(&SPACES[..len])?;
+        n -= len;
+    }
+
+    Ok(())
+}
+
+/// Part of a [`Canvas`] starting at a given column and line, optionally limited in width
+#[derive(Debug)]
+pub struct Region<'a> {
+    canvas: &'a mut Canvas,
+    x: usize,
+    y: usize,
+    width: Option<usize>,
+}
+
+impl Region<'_> {
+    /// Write `text` on the `row`-th line of the region
+    ///
+    /// Texts on the same line are placed one after another, so a line is written from left to right.
+    /// What goes beyond the width of the region is cut off.
+    pub fn write(&mut self, row: usize, text: &str) {
+        let end = self.width.map(|width| self.x + width);
+        self.canvas.put(self.x, self.y + row, text, end);
+    }
+
+    /// Sub-region starting `x` columns right and `y` lines down
+    pub fn region(&mut self, x: usize, y: usize) -> Region<'_> {
+        Region {
+            x: self.x + x,
+            y: self.y + y,
+            width: self.width.map(|width| width.saturating_sub(x)),
+            canvas: self.canvas,
+        }
+    }
+
+    /// Same region, at most `width` columns wide
+    pub fn with_width(self, width: usize) -> Self {
+        Self {
+            width: Some(self.width.map_or(width, |w| w.min(width))),
+            ..self
+        }
+    }
+}
+
+/// `fmt::Write` forwarding to an `io::Write`, keeping the I/O error that `fmt::Error` cannot carry
+pub(crate) struct IoWriter<'a> {
+    inner: &'a mut dyn io::Write,
+    error: Option<io::Error>,
+}
+
+impl<'a> IoWriter<'a> {
+    pub(crate) fn new(inner: &'a mut dyn io::Write) -> Self {
+        Self { inner, error: None }
+    }
+
+    /// Error of the last failed write
+    pub(crate) fn into_error(self) -> io::Error {
+        self.error
+            .unwrap_or_else(|| io::Error::other("formatter error"))
+    }
+}
+
+impl Write for IoWriter<'_> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|e| {
+            self.error = Some(e);
+            std::fmt::Error
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() {
+        let mut canvas = Canvas::new();
+        let mut region = canvas.region();
+        region.write(0, "ab");
+        region.region(4, 1).write(0, "\x1b[1mcd\x1b[0m");
+        region.region(4, 0).write(0, "ef");
+        region.write(1, "gh");
+
+        let mut output = String::new();
+        canvas.write_to(&mut output, 7, 3).unwrap();
+        assert_eq!(output, "ab  ef \n    \x1b[1mcd\x1b[0mgh\n       ");
+
+        // a child wider than its region does not push its neighbour
+        let mut canvas = Canvas::new();
+        let mut region = canvas.region();
+        region.region(0, 0).with_width(3).write(0, "abcdef");
+        region
+            .region(0, 1)
+            .with_width(3)
+            .write(0, "\x1b[4mabcd\x1b[0m");
+        region.region(4, 0).write(0, "gh");
+        let lines: Vec<&str> = canvas.lines().collect();
+        assert_eq!(lines, ["abc gh", "\x1b[4mabc\x1b[0m"]);
+    }
+}
@@ -0,0 +1,122 @@
+use std::{fmt::Debug, rc::Rc};
+
+use crate::{BasicMarker, Marker};
+
+/// Kind of mark (e.g. "holiday", "on-call") drawn with its own marker
+#[derive(Clone)]
+pub struct Category {
+    name: String,
+    marker: Rc<dyn Marker>,
+    priority: i32,
+}
+
+impl Category {
+    /// Category of [`Calendar::mark`](crate::Calendar::mark)
+    pub const MARKED: &'static str = "marked";
+
+    /// Category of [`Calendar::mark_holiday`](crate::Calendar::mark_holiday)
+    pub const HOLIDAY: &'static str = "holiday";
+
+    pub fn new(name: impl Into<String>, marker: impl Marker + 'static) -> Self {
+        Self::from_rc(name, Rc::new(marker))
+    }
+
+    pub fn from_rc(name: impl Into<String>, marker: Rc<dyn Marker>) -> Self {
+        Self {
+            name: name.into(),
+            marker,
+            priority: 0,
+        }
+    }
+
+    /// Categories with higher priority win when a day has several categories (default: 0)
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    pub(crate) fn holiday() -> Self {
+        Self::new(Self::HOLIDAY, BasicMarker::Char('*')).with_priority(-1)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn marker(&self) -> &dyn Marker {
+        self.marker.as_ref()
+    }
+
+    pub fn priority(&self) -> i32 {
+        self.priority
+    }
+}
+
+impl PartialEq for Category {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl Debug for Category {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Category")
+            .field("name", &self.name)
+            .field("marker", &self.marker)
+            .field("priority", &self.priority)
+            .finish()
+    }
+}
+
+/// How a day with several categories is decorated
+#[derive(Clone, Default)]
+pub enum MarkerCombination {
+    /// Only the marker of the category with the highest priority
+    /// (the earliest one on ties)
+    #[default]
+    Priority,
+
+    /// All markers, the one with the highest priority innermost
+    ///
+    /// note: the result can be wider than the day width
+    Nested,
+
+    /// A dedicated marker for days with more than one category
+    Marker(Rc<dyn Marker>),
+}
+
+impl MarkerCombination {
+    /// Decorate `day` with `categories` sorted by precedence
+    ///
+    /// `value` is the value of the day with all the values of the calendar.
+    pub(crate) fn decorate(
+        &self,
+        day: &str,
+        categories: &[&Category],
+        value: Option<(f64, &[f64])>,
+    ) -> String {
+        let decorate = |marker: &dyn Marker, day: &str| match value {
+            Some((value, values)) => marker.decorate_value(day, value, values),
+            None => marker.decorate(day),
+        };
+
+        match (self, categories) {
+            (_, []) => day.to_string(),
+            (MarkerCombination::Nested, _) => categories
+                .iter()
+                .fold(day.to_string(), |day, c| decorate(c.marker(), &day)),
+            (MarkerCombination::Marker(marker), [_, _, ..]) => decorate(marker.as_ref(), day),
+            (_, [first, ..]) => decorate(first.marker(), day),
+        }
+    }
+}
+
+impl Debug for MarkerCombination {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MarkerCombination::Priority => write!(f, "Priority"),
+            MarkerCombination::Nested => write!(f, "Nested"),
+            MarkerCombination::Marker(marker) => write!(f, "Marker({:?})", marker),
+        }
+    }
+}
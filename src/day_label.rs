@@ -0,0 +1,17 @@
+use std::fmt::Debug;
+
+use chrono::NaiveDate;
+
+/// Secondary text shown in a row under the day numbers
+pub trait DayLabel {
+    /// Label for the date
+    ///
+    /// note: text wider than the day width is cut
+    fn label(&self, date: NaiveDate) -> Option<String>;
+}
+
+impl Debug for dyn DayLabel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DayLabel")
+    }
+}
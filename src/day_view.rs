@@ -0,0 +1,317 @@
+use std::{
+    collections::BTreeSet,
+    fmt::Display,
+    ops::{Range, RangeInclusive},
+    rc::Rc,
+};
+
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime};
+
+use crate::{
+    utils::{center, display_width, pad, truncate},
+    BasicMarker, Calendar, ICalendar, MarkStore, Marker, Region,
+};
+
+/// Width of the time labels on the left (`09:00 `)
+const TIME_WIDTH: usize = 6;
+
+/// Block of time with a title
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimedEvent {
+    pub title: String,
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+}
+
+/// Timeline of one or more days: rows are time slots and columns are days
+///
+/// ```text
+///        Mon 06/03    Tue 06/04
+/// 09:00 ▌Standup     ▌Standup
+/// 09:30              ▌Incident r…
+/// 10:00              ▌
+/// ```
+/// Marked days (and days with all-day events) have their header decorated with the marker.
+#[derive(Debug)]
+pub struct DayView {
+    start: NaiveDate,
+    days: usize,
+    slot_minutes: u32,
+    hours: Range<u32>,
+    column_width: usize,
+    marker: Rc<dyn Marker>,
+    marks: BTreeSet<NaiveDate>,
+    store: Option<Rc<dyn MarkStore>>,
+    events: Vec<TimedEvent>,
+}
+
+impl DayView {
+    /// `days` days from `start`, with slots of `slot_minutes` minutes (a divisor of 60)
+    pub fn new(start: NaiveDate, days: usize, slot_minutes: u32) -> Option<Self> {
+        if days == 0 || slot_minutes == 0 || 60 % slot_minutes != 0 {
+            return None;
+        }
+
+        Some(Self {
+            start,
+            days,
+            slot_minutes,
+            hours: 0..24,
+            column_width: 12,
+            marker: Rc::new(BasicMarker::SquareBrackets),
+            marks: BTreeSet::new(),
+            store: None,
+            events: vec![],
+        })
+    }
+
+    /// Hours shown (default: `0..24`)
+    pub fn set_hours(&mut self, hours: Range<u32>) {
+        self.hours = hours.start.min(24)..hours.end.min(24);
+    }
+
+    /// Width of a day column (default: 12)
+    pub fn set_column_width(&mut self, width: usize) {
+        self.column_width = width.max(1);
+    }
+
+    pub fn set_marker(&mut self, marker: impl Marker + 'static) {
+        self.marker = Rc::new(marker);
+    }
+
+    pub fn add_event(
+        &mut self,
+        title: impl Into<String>,
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+    ) {
+        self.events.push(TimedEvent {
+            title: title.into(),
+            start,
+            end: end.max(start),
+        });
+    }
+
+    /// Add the timed events of `ics` (and mark the days of its all-day events)
+    pub fn add_events(&mut self, ics: &ICalendar) {
+        let range = self.range();
+
+        for event in ics.events() {
+            if event.times.is_none() {
+                for date in event.dates(range.clone()) {
+                    self.mark(date);
+                }
+                continue;
+            }
+
+            for (start, end) in event.times_between(range.clone()) {
+                self.add_event(event.summary.clone().unwrap_or_default(), start, end);
+            }
+        }
+    }
+
+    pub fn events(&self) -> &[TimedEvent] {
+        &self.events
+    }
+
+    fn range(&self) -> RangeInclusive<NaiveDate> {
+        self.start..=self.start + Duration::days(self.days as i64 - 1)
+    }
+
+    /// Start times of the slots of a day
+    fn slots(&self) -> impl Iterator<Item = NaiveTime> + '_ {
+        self.hours.clone().flat_map(move |hour| {
+            (0..60)
+                .step_by(self.slot_minutes as usize)
+                .filter_map(move |minute| NaiveTime::from_hms_opt(hour, minute, 0))
+        })
+    }
+
+    fn fmt_header(&self, date: NaiveDate) -> String {
+        let label = date.format("%a %m/%d").to_string();
+
+        let label = if self.is_marked(date) {
+            self.marker.decorate(&label)
+        } else {
+            label
+        };
+
+        center(&label, self.column_width)
+    }
+
+    fn fmt_slot(&self, slot_start: NaiveDateTime) -> String {
+        let slot_end = slot_start + Duration::minutes(self.slot_minutes as i64);
+
+        let overlapping: Vec<&TimedEvent> = self
+            .events
+            .iter()
+            .filter(|e| e.start < slot_end && slot_start < e.end)
+            .collect();
+        if overlapping.is_empty() {
+            return " ".repeat(self.column_width);
+        }
+
+        // titles of the events starting in this slot (or at the top of the view)
+        let titles: Vec<&str> = overlapping
+            .iter()
+            .filter(|e| e.start >= slot_start || slot_start.time() == self.first_slot())
+            .map(|e| e.title.as_str())
+            .collect();
+
+        let text = format!("▌{}", titles.join(", "));
+        let width = self.column_width;
+
+        if display_width(&text) > width {
+            pad(&format!("{}…", truncate(&text, width - 1)), width)
+        } else {
+            pad(&text, width)
+        }
+    }
+
+    fn first_slot(&self) -> NaiveTime {
+        self.slots().next().unwrap_or(NaiveTime::MIN)
+    }
+}
+
+impl Calendar for DayView {
+    fn mark(&mut self, date: NaiveDate) {
+        if self.range().contains(&date) {
+            self.marks.insert(date);
+        }
+    }
+
+    fn unmark(&mut self, date: NaiveDate) {
+        self.marks.remove(&date);
+    }
+
+    fn is_marked(&self, date: NaiveDate) -> bool {
+        self.marks.contains(&date)
+            || self.range().contains(&date)
+                && self
+                    .store
+                    .as_ref()
+                    .is_some_and(|store| store.contains(date))
+    }
+
+    fn width(&self) -> usize {
+        TIME_WIDTH + self.days * (self.column_width + 1) - 1
+    }
+
+    fn height(&self) -> usize {
+        1 + self.slots().count()
+    }
+
+    fn draw(&self, region: &mut Region<'_>) {
+        let dates: Vec<NaiveDate> = self.start.iter_days().take(self.days).collect();
+
+        let header: Vec<String> = dates.iter().map(|d| self.fmt_header(*d)).collect();
+        region.write(
+            0,
+            &format!("{}{}", " ".repeat(TIME_WIDTH), header.join(" ")),
+        );
+
+        for (row, slot) in self.slots().enumerate() {
+            let cells: Vec<String> = dates
+                .iter()
+                .map(|d| self.fmt_slot(d.and_time(slot)))
+                .collect();
+
+            region.write(
+                row + 1,
+                &format!(
+                    "{: <width$}{}",
+                    slot.format("%H:%M"),
+                    cells.join(" "),
+                    width = TIME_WIDTH
+                ),
+            );
+        }
+    }
+
+    fn date_range(&self) -> Option<RangeInclusive<NaiveDate>> {
+        Some(self.range())
+    }
+
+    fn set_mark_store(&mut self, store: Option<Rc<dyn MarkStore>>) {
+        self.store = store;
+    }
+}
+
+impl Display for DayView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.render(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::n_date;
+
+    use super::*;
+
+    #[test]
+    fn test() {
+        let mut view = DayView::new(n_date!(2024, 6, 3), 2, 30).unwrap();
+        view.set_hours(9..11);
+        view.add_event(
+            "Standup",
+            n_date!(2024, 6, 3).and_hms_opt(9, 0, 0).unwrap(),
+            n_date!(2024, 6, 3).and_hms_opt(9, 15, 0).unwrap(),
+        );
+        view.add_event(
+            "Incident review",
+            n_date!(2024, 6, 4).and_hms_opt(9, 30, 0).unwrap(),
+            n_date!(2024, 6, 4).and_hms_opt(10, 30, 0).unwrap(),
+        );
+        view.mark(n_date!(2024, 6, 4));
+
+        let output = view.to_string();
+        let lines: Vec<&str> = output.split('\n').collect();
+
+        assert_eq!(lines.len(), view.height());
+        assert!(lines.iter().all(|l| display_width(l) == view.width()));
+        assert_eq!(
+            lines.iter().map(|l| l.trim_end()).collect::<Vec<_>>(),
+            [
+                "       Mon 06/03   [Tue 06/04]",
+                "09:00 ▌Standup",
+                "09:30              ▌Incident r…",
+                "10:00              ▌",
+                "10:30",
+            ]
+        );
+
+        assert!(DayView::new(n_date!(2024, 6, 3), 1, 45).is_none());
+
+        let ics: ICalendar = "BEGIN:VEVENT\n\
+                              DTSTART:20240527T093000\n\
+                              DURATION:PT30M\n\
+                              RRULE:FREQ=DAILY\n\
+                              SUMMARY:Handoff\n\
+                              END:VEVENT"
+            .parse()
+            .unwrap();
+        view.add_events(&ics);
+        assert_eq!(view.events().len(), 4);
+        assert!(view.to_string().contains("09:30 ▌Handoff     ▌Incident r…"));
+
+        // Tuesdays at 23:00 UTC land on Wednesdays in Tokyo
+        let ics = ICalendar::parse(
+            "BEGIN:VEVENT\n\
+             DTSTART:20240604T230000Z\n\
+             DURATION:PT1H\n\
+             RRULE:FREQ=WEEKLY;BYDAY=TU\n\
+             SUMMARY:Sync\n\
+             END:VEVENT",
+            chrono::FixedOffset::east_opt(9 * 3600).unwrap(),
+        )
+        .unwrap();
+        let mut view = DayView::new(n_date!(2024, 6, 10), 3, 30).unwrap();
+        view.add_events(&ics);
+        assert_eq!(view.events().len(), 1);
+        assert_eq!(
+            view.events()[0].start,
+            n_date!(2024, 6, 12).and_hms_opt(8, 0, 0).unwrap()
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use std::fmt::Display;
 
-use crate::Calendar;
+use crate::{Calendar, Region};
 
 #[derive(Debug, Default)]
 pub struct EmptyCalendar {
@@ -29,18 +29,16 @@ impl Calendar for EmptyCalendar {
     fn width(&self) -> usize {
         self.width
     }
+
+    fn draw(&self, region: &mut Region<'_>) {
+        for row in 0..self.height {
+            region.write(row, &" ".repeat(self.width));
+        }
+    }
 }
 
 impl Display for EmptyCalendar {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for i in 0..(self.height) {
-            write!(f, "{: ^width$}", "", width = self.width)?;
-
-            if i != self.height - 1 {
-                writeln!(f)?;
-            }
-        }
-
-        Ok(())
+        self.render(f)
     }
 }
@@ -0,0 +1,47 @@
+use std::fmt::Display;
+
+/// Errors of this crate
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// A recurrence rule (RRULE) could not be parsed
+    InvalidRecurrence(String),
+    /// An iCalendar (.ics) file could not be parsed
+    InvalidICalendar(String),
+    /// The options of a [`MonthCalendarBuilder`](crate::MonthCalendarBuilder) do not fit together
+    InvalidMonthCalendar(String),
+    /// Not even one calendar per row fits in the available width
+    TooNarrow { available: usize, required: usize },
+    /// Not even one row of calendars fits in the available height
+    TooShort { available: usize, required: usize },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::InvalidRecurrence(message) => write!(f, "invalid recurrence rule: {}", message),
+            Error::InvalidICalendar(message) => write!(f, "invalid iCalendar: {}", message),
+            Error::InvalidMonthCalendar(message) => {
+                write!(f, "invalid month calendar: {}", message)
+            }
+            Error::TooNarrow {
+                available,
+                required,
+            } => write!(
+                f,
+                "too narrow: {} columns are required, but only {} are available",
+                required, available
+            ),
+            Error::TooShort {
+                available,
+                required,
+            } => write!(
+                f,
+                "too short: {} lines are required, but only {} are available",
+                required, available
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
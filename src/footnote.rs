@@ -0,0 +1,44 @@
+/// How footnote references are shown next to annotated days
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FootnoteStyle {
+    /// Superscript numbers `¹ ² ³ … ¹⁰`
+    #[default]
+    Numbers,
+
+    /// Symbols `* † ‡ § ‖ ¶`, doubled after the sixth (`**`, `††`, …)
+    Symbols,
+}
+
+const SUPERSCRIPTS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+const SYMBOLS: [char; 6] = ['*', '†', '‡', '§', '‖', '¶'];
+
+impl FootnoteStyle {
+    /// Reference of the `n`-th footnote (starting from 1)
+    ///
+    /// `0` is `⁰` with numbers and has no symbol (an empty string).
+    pub fn reference(&self, n: usize) -> String {
+        match self {
+            FootnoteStyle::Symbols if n == 0 => String::new(),
+            FootnoteStyle::Numbers => n
+                .to_string()
+                .chars()
+                .map(|c| SUPERSCRIPTS[c.to_digit(10).unwrap() as usize])
+                .collect(),
+            FootnoteStyle::Symbols => SYMBOLS[(n - 1) % SYMBOLS.len()]
+                .to_string()
+                .repeat((n - 1) / SYMBOLS.len() + 1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reference() {
+        assert_eq!(FootnoteStyle::Numbers.reference(12), "¹²");
+        assert_eq!(FootnoteStyle::Symbols.reference(8), "††");
+        assert_eq!(FootnoteStyle::Symbols.reference(0), "");
+    }
+}
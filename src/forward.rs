@@ -0,0 +1,246 @@
+//! Forwarding of the [`Calendar`](crate::Calendar) methods to the calendars inside a container
+//!
+//! Containers write their layout methods (`width`, `height`, `draw` and `render`) and get
+//! the others from one of these macros, so a new method of the trait is added here once.
+
+/// Methods of `Calendar` but the layout ones, forwarded to the calendar in `self.$field`
+macro_rules! forward_calendar {
+    ($field: ident) => {
+        fn is_marked(&self, date: ::chrono::NaiveDate) -> bool {
+            self.$field.is_marked(date)
+        }
+
+        fn mark(&mut self, date: ::chrono::NaiveDate) {
+            self.$field.mark(date)
+        }
+
+        fn unmark(&mut self, date: ::chrono::NaiveDate) {
+            self.$field.unmark(date)
+        }
+
+        fn mark_range(&mut self, range: ::std::ops::RangeInclusive<::chrono::NaiveDate>) {
+            self.$field.mark_range(range)
+        }
+
+        fn unmark_range(&mut self, range: ::std::ops::RangeInclusive<::chrono::NaiveDate>) {
+            self.$field.unmark_range(range)
+        }
+
+        fn set_range_marker(&mut self, marker: ::std::rc::Rc<dyn $crate::RangeMarker>) {
+            self.$field.set_range_marker(marker)
+        }
+
+        fn date_range(&self) -> Option<::std::ops::RangeInclusive<::chrono::NaiveDate>> {
+            self.$field.date_range()
+        }
+
+        fn annotate(&mut self, date: ::chrono::NaiveDate, text: &str) {
+            self.$field.annotate(date, text)
+        }
+
+        fn remove_annotations(&mut self, date: ::chrono::NaiveDate) {
+            self.$field.remove_annotations(date)
+        }
+
+        fn annotations(&self, date: ::chrono::NaiveDate) -> Vec<String> {
+            self.$field.annotations(date)
+        }
+
+        fn mark_holiday(&mut self, holiday: &$crate::Holiday) {
+            self.$field.mark_holiday(holiday)
+        }
+
+        fn holidays(&self) -> Vec<$crate::Holiday> {
+            self.$field.holidays()
+        }
+
+        fn set_holiday_marker(&mut self, marker: ::std::rc::Rc<dyn $crate::Marker>) {
+            self.$field.set_holiday_marker(marker)
+        }
+
+        fn set_value(&mut self, date: ::chrono::NaiveDate, value: f64) {
+            self.$field.set_value(date, value)
+        }
+
+        fn value(&self, date: ::chrono::NaiveDate) -> Option<f64> {
+            self.$field.value(date)
+        }
+
+        fn set_today_highlight(&mut self, highlight: Option<$crate::TodayHighlight>) {
+            self.$field.set_today_highlight(highlight)
+        }
+
+        fn set_weekend_style(&mut self, style: Option<$crate::WeekendStyle>) {
+            self.$field.set_weekend_style(style)
+        }
+
+        fn set_mark_store(&mut self, store: Option<::std::rc::Rc<dyn $crate::MarkStore>>) {
+            self.$field.set_mark_store(store)
+        }
+
+        fn mark_category(&mut self, date: ::chrono::NaiveDate, category: &$crate::Category) {
+            self.$field.mark_category(date, category)
+        }
+
+        fn unmark_category(&mut self, date: ::chrono::NaiveDate, name: &str) {
+            self.$field.unmark_category(date, name)
+        }
+
+        fn categories_of(&self, date: ::chrono::NaiveDate) -> Vec<$crate::Category> {
+            self.$field.categories_of(date)
+        }
+
+        fn categories(&self) -> Vec<$crate::Category> {
+            self.$field.categories()
+        }
+
+        fn set_combination(&mut self, combination: $crate::MarkerCombination) {
+            self.$field.set_combination(combination)
+        }
+    };
+}
+
+/// Methods of `Calendar` but the layout ones, forwarded to every calendar of the
+/// `Vec<Box<dyn Calendar>>` in `self.$field`
+///
+/// Setters are passed to every calendar; queries merge their answers.
+macro_rules! forward_calendar_to_all {
+    ($field: ident) => {
+        fn is_marked(&self, date: ::chrono::NaiveDate) -> bool {
+            self.$field.iter().any(|c| c.is_marked(date))
+        }
+
+        fn mark(&mut self, date: ::chrono::NaiveDate) {
+            self.$field.iter_mut().for_each(|c| c.mark(date));
+        }
+
+        fn unmark(&mut self, date: ::chrono::NaiveDate) {
+            self.$field.iter_mut().for_each(|c| c.unmark(date));
+        }
+
+        fn mark_range(&mut self, range: ::std::ops::RangeInclusive<::chrono::NaiveDate>) {
+            self.$field
+                .iter_mut()
+                .for_each(|c| c.mark_range(range.clone()));
+        }
+
+        fn unmark_range(&mut self, range: ::std::ops::RangeInclusive<::chrono::NaiveDate>) {
+            self.$field
+                .iter_mut()
+                .for_each(|c| c.unmark_range(range.clone()));
+        }
+
+        fn set_range_marker(&mut self, marker: ::std::rc::Rc<dyn $crate::RangeMarker>) {
+            self.$field
+                .iter_mut()
+                .for_each(|c| c.set_range_marker(marker.clone()));
+        }
+
+        fn date_range(&self) -> Option<::std::ops::RangeInclusive<::chrono::NaiveDate>> {
+            let ranges: Vec<::std::ops::RangeInclusive<::chrono::NaiveDate>> =
+                self.$field.iter().filter_map(|c| c.date_range()).collect();
+
+            let start = ranges.iter().map(|r| *r.start()).min()?;
+            let end = ranges.iter().map(|r| *r.end()).max()?;
+
+            Some(start..=end)
+        }
+
+        fn annotate(&mut self, date: ::chrono::NaiveDate, text: &str) {
+            self.$field.iter_mut().for_each(|c| c.annotate(date, text));
+        }
+
+        fn remove_annotations(&mut self, date: ::chrono::NaiveDate) {
+            self.$field
+                .iter_mut()
+                .for_each(|c| c.remove_annotations(date));
+        }
+
+        fn annotations(&self, date: ::chrono::NaiveDate) -> Vec<String> {
+            let mut annotations: Vec<String> = vec![];
+            for annotation in self.$field.iter().flat_map(|c| c.annotations(date)) {
+                if !annotations.contains(&annotation) {
+                    annotations.push(annotation);
+                }
+            }
+
+            annotations
+        }
+
+        fn mark_holiday(&mut self, holiday: &$crate::Holiday) {
+            self.$field.iter_mut().for_each(|c| c.mark_holiday(holiday));
+        }
+
+        fn holidays(&self) -> Vec<$crate::Holiday> {
+            let mut holidays: Vec<$crate::Holiday> =
+                self.$field.iter().flat_map(|c| c.holidays()).collect();
+            holidays.sort();
+            holidays.dedup();
+
+            holidays
+        }
+
+        fn set_holiday_marker(&mut self, marker: ::std::rc::Rc<dyn $crate::Marker>) {
+            self.$field
+                .iter_mut()
+                .for_each(|c| c.set_holiday_marker(marker.clone()));
+        }
+
+        fn set_value(&mut self, date: ::chrono::NaiveDate, value: f64) {
+            self.$field
+                .iter_mut()
+                .for_each(|c| c.set_value(date, value));
+        }
+
+        fn value(&self, date: ::chrono::NaiveDate) -> Option<f64> {
+            self.$field.iter().find_map(|c| c.value(date))
+        }
+
+        fn set_today_highlight(&mut self, highlight: Option<$crate::TodayHighlight>) {
+            self.$field
+                .iter_mut()
+                .for_each(|c| c.set_today_highlight(highlight.clone()));
+        }
+
+        fn set_weekend_style(&mut self, style: Option<$crate::WeekendStyle>) {
+            self.$field
+                .iter_mut()
+                .for_each(|c| c.set_weekend_style(style.clone()));
+        }
+
+        fn set_mark_store(&mut self, store: Option<::std::rc::Rc<dyn $crate::MarkStore>>) {
+            self.$field
+                .iter_mut()
+                .for_each(|c| c.set_mark_store(store.clone()));
+        }
+
+        fn mark_category(&mut self, date: ::chrono::NaiveDate, category: &$crate::Category) {
+            self.$field
+                .iter_mut()
+                .for_each(|c| c.mark_category(date, category));
+        }
+
+        fn unmark_category(&mut self, date: ::chrono::NaiveDate, name: &str) {
+            self.$field
+                .iter_mut()
+                .for_each(|c| c.unmark_category(date, name));
+        }
+
+        fn categories_of(&self, date: ::chrono::NaiveDate) -> Vec<$crate::Category> {
+            $crate::calendars::merge_categories(self.$field.iter().map(|c| c.categories_of(date)))
+        }
+
+        fn categories(&self) -> Vec<$crate::Category> {
+            $crate::calendars::merge_categories(self.$field.iter().map(|c| c.categories()))
+        }
+
+        fn set_combination(&mut self, combination: $crate::MarkerCombination) {
+            self.$field
+                .iter_mut()
+                .for_each(|c| c.set_combination(combination.clone()));
+        }
+    };
+}
+
+pub(crate) use forward_calendar;
+pub(crate) use forward_calendar_to_all;
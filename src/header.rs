@@ -0,0 +1,123 @@
+use std::{fmt::Debug, rc::Rc};
+
+use chrono::NaiveDate;
+
+use crate::Alignment;
+
+/// Content of the title line of a [`MonthCalendar`](crate::MonthCalendar)
+#[derive(Clone, Default)]
+pub enum TitleFormat {
+    /// `June`
+    #[default]
+    Month,
+
+    /// `June 2024`
+    MonthYear,
+
+    /// `2024-06`
+    YearMonth,
+
+    /// Title made from the first day of the month
+    Custom(Rc<dyn Fn(NaiveDate) -> String>),
+}
+
+impl TitleFormat {
+    pub fn custom(f: impl Fn(NaiveDate) -> String + 'static) -> Self {
+        Self::Custom(Rc::new(f))
+    }
+
+    pub fn title(&self, first_day: NaiveDate) -> String {
+        match self {
+            TitleFormat::Month => first_day.format("%B").to_string(),
+            TitleFormat::MonthYear => first_day.format("%B %Y").to_string(),
+            TitleFormat::YearMonth => first_day.format("%Y-%m").to_string(),
+            TitleFormat::Custom(f) => f(first_day),
+        }
+    }
+}
+
+impl Debug for TitleFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TitleFormat::Month => write!(f, "Month"),
+            TitleFormat::MonthYear => write!(f, "MonthYear"),
+            TitleFormat::YearMonth => write!(f, "YearMonth"),
+            TitleFormat::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+
+/// Lines above the grid of a [`MonthCalendar`](crate::MonthCalendar): the title, an optional
+/// subtitle and the weekday row
+///
+/// ```
+/// use text_calendar::{Alignment, Header, TitleFormat};
+///
+/// let header = Header::new(TitleFormat::MonthYear)
+///     .with_alignment(Alignment::Left)
+///     .with_subtitle("Platform team")
+///     .without_weekdays();
+/// assert_eq!(header.height(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Header {
+    format: TitleFormat,
+    alignment: Alignment,
+    subtitle: Option<String>,
+    weekdays: bool,
+}
+
+impl Default for Header {
+    fn default() -> Self {
+        Self::new(TitleFormat::default())
+    }
+}
+
+impl Header {
+    pub fn new(format: TitleFormat) -> Self {
+        Self {
+            format,
+            alignment: Alignment::default(),
+            subtitle: None,
+            weekdays: true,
+        }
+    }
+
+    /// Alignment of the title and the subtitle
+    pub fn with_alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    pub fn with_subtitle(mut self, subtitle: impl Into<String>) -> Self {
+        self.subtitle = Some(subtitle.into());
+        self
+    }
+
+    /// Hide the weekday row
+    pub fn without_weekdays(mut self) -> Self {
+        self.weekdays = false;
+        self
+    }
+
+    pub fn alignment(&self) -> Alignment {
+        self.alignment
+    }
+
+    pub fn subtitle(&self) -> Option<&str> {
+        self.subtitle.as_deref()
+    }
+
+    pub fn has_weekdays(&self) -> bool {
+        self.weekdays
+    }
+
+    pub fn title(&self, first_day: NaiveDate) -> String {
+        self.format.title(first_day)
+    }
+
+    /// Number of lines
+    pub fn height(&self) -> usize {
+        1 + self.subtitle.is_some() as usize + self.weekdays as usize
+    }
+}
@@ -0,0 +1,295 @@
+use std::{collections::BTreeMap, fmt::Display, ops::RangeInclusive, rc::Rc};
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+use crate::{Calendar, MarkStore, Region};
+
+/// How the intensity of a day is drawn
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum HeatmapStyle {
+    /// ` ░▒▓█`
+    #[default]
+    Glyphs,
+
+    /// Background colors of the 256-color palette (gray, then four greens)
+    Ansi,
+}
+
+const GLYPHS: [char; 5] = [' ', '░', '▒', '▓', '█'];
+const ANSI_COLORS: [u8; 5] = [237, 22, 28, 34, 40];
+
+/// Width of the weekday labels on the left (`Mo `)
+const LABEL_WIDTH: usize = 3;
+
+/// Contribution-graph style view of per-day values
+///
+/// Weeks are columns and weekdays are rows, with month labels along the top:
+/// ```text
+///    Jan Feb Mar
+/// Su  ░▒  ░  █  ░
+/// Mo ▓  ░░ ▒   ░ ▒
+/// ...
+/// ```
+/// `mark` adds 1 to the value of the day and `unmark` clears it, so dates of events
+/// (e.g. commits) can be marked one by one. The dates of a [`MarkStore`] set with
+/// `set_mark_store` count 1 more each time the heatmap is drawn.
+#[derive(Debug, Clone)]
+pub struct Heatmap {
+    start: NaiveDate,
+    end: NaiveDate,
+    begin_weekday: Weekday,
+    values: BTreeMap<NaiveDate, f64>,
+    style: HeatmapStyle,
+    store: Option<Rc<dyn MarkStore>>,
+}
+
+impl Heatmap {
+    /// Heatmap of the days from `start` to `end` (inclusive)
+    pub fn new(start: NaiveDate, end: NaiveDate, begin_weekday: Weekday) -> Self {
+        Self {
+            start,
+            end: end.max(start),
+            begin_weekday,
+            values: BTreeMap::new(),
+            style: HeatmapStyle::default(),
+            store: None,
+        }
+    }
+
+    pub fn from_year(year: i32, begin_weekday: Weekday) -> Option<Self> {
+        Some(Self::new(
+            NaiveDate::from_ymd_opt(year, 1, 1)?,
+            NaiveDate::from_ymd_opt(year, 12, 31)?,
+            begin_weekday,
+        ))
+    }
+
+    pub fn set_style(&mut self, style: HeatmapStyle) {
+        self.style = style;
+    }
+
+    pub fn add_value(&mut self, date: NaiveDate, value: f64) {
+        if self.contains(date) {
+            *self.values.entry(date).or_default() += value;
+        }
+    }
+
+    fn contains(&self, date: NaiveDate) -> bool {
+        self.start <= date && date <= self.end
+    }
+
+    /// First day of the first column
+    fn first_week(&self) -> NaiveDate {
+        let back = (self.start.weekday().num_days_from_monday() + 7
+            - self.begin_weekday.num_days_from_monday())
+            % 7;
+
+        self.start - Duration::days(back as i64)
+    }
+
+    fn columns(&self) -> usize {
+        ((self.end - self.first_week()).num_days() / 7 + 1) as usize
+    }
+
+    fn max_value(&self) -> f64 {
+        let stored = self
+            .store
+            .as_ref()
+            .map(|store| store.marked_between(self.start..=self.end))
+            .unwrap_or_default();
+
+        self.values
+            .keys()
+            .chain(&stored)
+            .filter_map(|date| self.value(*date))
+            .fold(0.0, f64::max)
+    }
+
+    /// 0 for no value, 1..=4 relative to the largest value
+    fn level(&self, date: NaiveDate, max: f64) -> usize {
+        match self.value(date) {
+            Some(value) if value > 0.0 && max > 0.0 => {
+                ((value / max * 4.0).ceil() as usize).clamp(1, 4)
+            }
+            _ => 0,
+        }
+    }
+
+    fn fmt_cell(&self, date: NaiveDate, max: f64) -> String {
+        if !self.contains(date) {
+            return " ".to_string();
+        }
+
+        let level = self.level(date, max);
+        match self.style {
+            HeatmapStyle::Glyphs => GLYPHS[level].to_string(),
+            HeatmapStyle::Ansi => format!("\x1b[48;5;{}m \x1b[0m", ANSI_COLORS[level]),
+        }
+    }
+
+    fn month_labels(&self) -> String {
+        let width = self.columns();
+        let first_week = self.first_week();
+        let mut line = vec![' '; width];
+        let mut free = 0;
+
+        let firsts = std::iter::successors(Some(self.start), |d| {
+            let next =
+                NaiveDate::from_ymd_opt(d.year() + d.month() as i32 / 12, d.month() % 12 + 1, 1)?;
+            Some(next).filter(|n| *n <= self.end)
+        });
+
+        for first in firsts {
+            let column = ((first - first_week).num_days() / 7) as usize;
+            if column < free {
+                continue;
+            }
+
+            let label = first.format("%b").to_string();
+            for (i, c) in label.chars().enumerate() {
+                if let Some(cell) = line.get_mut(column + i) {
+                    *cell = c;
+                }
+            }
+            free = column + label.chars().count() + 1;
+        }
+
+        line.into_iter().collect()
+    }
+}
+
+impl Calendar for Heatmap {
+    fn mark(&mut self, date: NaiveDate) {
+        self.add_value(date, 1.0);
+    }
+
+    fn unmark(&mut self, date: NaiveDate) {
+        self.values.remove(&date);
+    }
+
+    fn is_marked(&self, date: NaiveDate) -> bool {
+        self.value(date).is_some_and(|value| value > 0.0)
+    }
+
+    fn width(&self) -> usize {
+        LABEL_WIDTH + self.columns()
+    }
+
+    fn height(&self) -> usize {
+        1 + 7
+    }
+
+    fn draw(&self, region: &mut Region<'_>) {
+        region.write(
+            0,
+            &format!("{}{}", " ".repeat(LABEL_WIDTH), self.month_labels()),
+        );
+
+        let first_week = self.first_week();
+        let max = self.max_value();
+        let mut weekday = self.begin_weekday;
+
+        for row in 0..7 {
+            let mut line = format!(
+                "{: <width$}",
+                &weekday.to_string()[..2],
+                width = LABEL_WIDTH
+            );
+
+            for column in 0..self.columns() {
+                let date = first_week + Duration::days((column * 7 + row) as i64);
+                line.push_str(&self.fmt_cell(date, max));
+            }
+
+            region.write(row + 1, &line);
+            weekday = weekday.succ();
+        }
+    }
+
+    fn date_range(&self) -> Option<RangeInclusive<NaiveDate>> {
+        Some(self.start..=self.end)
+    }
+
+    fn set_value(&mut self, date: NaiveDate, value: f64) {
+        if self.contains(date) {
+            self.values.insert(date, value);
+        }
+    }
+
+    fn value(&self, date: NaiveDate) -> Option<f64> {
+        let value = self.values.get(&date).copied();
+        let stored = self.contains(date)
+            && self
+                .store
+                .as_ref()
+                .is_some_and(|store| store.contains(date));
+
+        match (value, stored) {
+            (Some(value), true) => Some(value + 1.0),
+            (None, true) => Some(1.0),
+            (value, false) => value,
+        }
+    }
+
+    fn set_mark_store(&mut self, store: Option<Rc<dyn MarkStore>>) {
+        self.store = store;
+    }
+}
+
+impl Display for Heatmap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.render(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, collections::BTreeSet};
+
+    use crate::{n_date, utils::display_width, Calendars};
+
+    use super::*;
+
+    #[test]
+    fn test() {
+        let mut heatmap = Heatmap::new(n_date!(2024, 1, 1), n_date!(2024, 3, 31), Weekday::Sun);
+        heatmap.set_value(n_date!(2024, 1, 1), 1.0);
+        heatmap.set_value(n_date!(2024, 1, 2), 4.0);
+        heatmap.mark(n_date!(2024, 1, 3));
+        heatmap.mark(n_date!(2024, 1, 3));
+
+        let output = heatmap.to_string();
+        let lines: Vec<&str> = output.split('\n').collect();
+
+        assert_eq!(lines.len(), heatmap.height());
+        assert!(lines
+            .iter()
+            .all(|line| display_width(line) == heatmap.width()));
+        assert!(lines[0].starts_with("   Jan Feb Mar"));
+        assert!(lines[2].starts_with("Mo ░"));
+        assert!(lines[3].starts_with("Tu █"));
+        assert!(lines[4].starts_with("We ▒"));
+        assert!(lines[1].starts_with("Su  "));
+
+        // the store is read when drawing
+        let store = Rc::new(RefCell::new(BTreeSet::new()));
+        let mut shared = heatmap.clone();
+        shared.set_mark_store(Some(store.clone()));
+        store.borrow_mut().insert(n_date!(2024, 1, 1));
+        store.borrow_mut().insert(n_date!(2024, 1, 4));
+        assert_eq!(shared.value(n_date!(2024, 1, 1)), Some(2.0));
+        let lines: Vec<String> = shared.to_string().split('\n').map(String::from).collect();
+        assert!(lines[2].starts_with("Mo ▒"));
+        assert!(lines[5].starts_with("Th ░"));
+
+        let calendars = Calendars::new(
+            vec![Box::new(heatmap.clone()), Box::new(heatmap)],
+            "".to_string(),
+            1,
+        );
+        assert_eq!(
+            calendars.to_string().split('\n').count(),
+            calendars.height()
+        );
+    }
+}
@@ -0,0 +1,540 @@
+use std::{collections::BTreeSet, fmt::Debug, ops::RangeInclusive};
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+/// Named holiday
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Holiday {
+    pub date: NaiveDate,
+    pub name: String,
+}
+
+impl Holiday {
+    pub fn new(date: NaiveDate, name: impl Into<String>) -> Self {
+        Self {
+            date,
+            name: name.into(),
+        }
+    }
+}
+
+/// Source of holidays
+pub trait HolidayProvider {
+    /// Holidays in the year, in date order
+    fn holidays(&self, year: i32) -> Vec<Holiday>;
+}
+
+impl Debug for dyn HolidayProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "HolidayProvider")
+    }
+}
+
+/// How to find the date of a holiday in a year
+#[derive(Debug, Clone)]
+pub enum DateRule {
+    /// Same month and day every year
+    Fixed { month: u32, day: u32 },
+
+    /// n-th weekday of the month, counted from the end when `n` is negative
+    /// (e.g. `-1` is the last one)
+    NthWeekday {
+        month: u32,
+        weekday: Weekday,
+        n: i32,
+    },
+
+    /// Days after Easter Sunday (negative for days before)
+    Easter(i64),
+
+    /// March equinox in Japan Standard Time (1980-2099)
+    VernalEquinox,
+
+    /// September equinox in Japan Standard Time (1980-2099)
+    AutumnalEquinox,
+}
+
+impl DateRule {
+    pub fn date(&self, year: i32) -> Option<NaiveDate> {
+        match self {
+            DateRule::Fixed { month, day } => NaiveDate::from_ymd_opt(year, *month, *day),
+            DateRule::NthWeekday { month, weekday, n } => nth_weekday(year, *month, *weekday, *n),
+            DateRule::Easter(offset) => easter(year)?.checked_add_signed(Duration::days(*offset)),
+            DateRule::VernalEquinox => equinox(year, 20.8431, 3),
+            DateRule::AutumnalEquinox => equinox(year, 23.2488, 9),
+        }
+    }
+}
+
+/// What happens when a holiday falls on a weekend
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Substitute {
+    /// No substitute day
+    None,
+
+    /// Saturday is observed on Friday and Sunday on Monday (United States)
+    NearestWeekday,
+
+    /// Saturday and Sunday are replaced by the next weekday that is not a holiday (United Kingdom)
+    NextWeekday,
+
+    /// Sunday is replaced by the next day that is not a holiday (Japan)
+    NextNonHoliday,
+}
+
+/// Holiday defined by a [`DateRule`]
+#[derive(Debug, Clone)]
+pub struct HolidayRule {
+    name: String,
+    date: DateRule,
+    substitute: Substitute,
+    years: RangeInclusive<i32>,
+    /// Years in which the holiday was moved, with its month and day that year
+    moves: Vec<(i32, u32, u32)>,
+}
+
+impl HolidayRule {
+    pub fn new(name: impl Into<String>, date: DateRule) -> Self {
+        Self {
+            name: name.into(),
+            date,
+            substitute: Substitute::None,
+            years: i32::MIN..=i32::MAX,
+            moves: vec![],
+        }
+    }
+
+    /// Set the substitute holiday rule
+    pub fn substitute(mut self, substitute: Substitute) -> Self {
+        self.substitute = substitute;
+        self
+    }
+
+    /// Limit the years in which the holiday exists
+    pub fn years(mut self, years: RangeInclusive<i32>) -> Self {
+        self.years = years;
+        self
+    }
+
+    /// Move the holiday to another day in `year` (e.g. a bank holiday moved for a jubilee)
+    pub fn moved(mut self, year: i32, month: u32, day: u32) -> Self {
+        self.moves.push((year, month, day));
+        self
+    }
+
+    /// Date of the holiday in `year`
+    fn date(&self, year: i32) -> Option<NaiveDate> {
+        match self.moves.iter().find(|(moved, _, _)| *moved == year) {
+            Some((_, month, day)) => NaiveDate::from_ymd_opt(year, *month, *day),
+            None => self.date.date(year),
+        }
+    }
+
+    fn fixed(name: &str, month: u32, day: u32) -> Self {
+        Self::new(name, DateRule::Fixed { month, day })
+    }
+
+    fn nth(name: &str, month: u32, weekday: Weekday, n: i32) -> Self {
+        Self::new(name, DateRule::NthWeekday { month, weekday, n })
+    }
+}
+
+/// Holidays generated from a list of [`HolidayRule`]s
+#[derive(Debug, Clone)]
+pub struct RuleBasedHolidays {
+    rules: Vec<HolidayRule>,
+    substitute_name: String,
+    bridge_name: Option<String>,
+}
+
+impl RuleBasedHolidays {
+    pub fn new(rules: Vec<HolidayRule>) -> Self {
+        Self {
+            rules,
+            substitute_name: String::from("{} (substitute)"),
+            bridge_name: None,
+        }
+    }
+
+    /// Name of substitute holidays, `{}` is replaced with the original name
+    pub fn substitute_name(mut self, name: impl Into<String>) -> Self {
+        self.substitute_name = name.into();
+        self
+    }
+
+    /// Make a day between two holidays a holiday too (Japanese citizens' holiday)
+    pub fn bridge_name(mut self, name: impl Into<String>) -> Self {
+        self.bridge_name = Some(name.into());
+        self
+    }
+
+    pub fn add(&mut self, rule: HolidayRule) {
+        self.rules.push(rule);
+    }
+
+    /// Federal holidays of the United States
+    pub fn us() -> Self {
+        use Weekday::*;
+
+        let rules = vec![
+            HolidayRule::fixed("New Year's Day", 1, 1),
+            HolidayRule::nth("Martin Luther King Jr. Day", 1, Mon, 3).years(1986..=i32::MAX),
+            HolidayRule::nth("Washington's Birthday", 2, Mon, 3),
+            HolidayRule::nth("Memorial Day", 5, Mon, -1),
+            HolidayRule::fixed("Juneteenth", 6, 19).years(2021..=i32::MAX),
+            HolidayRule::fixed("Independence Day", 7, 4),
+            HolidayRule::nth("Labor Day", 9, Mon, 1),
+            HolidayRule::nth("Columbus Day", 10, Mon, 2),
+            HolidayRule::fixed("Veterans Day", 11, 11),
+            HolidayRule::nth("Thanksgiving Day", 11, Thu, 4),
+            HolidayRule::fixed("Christmas Day", 12, 25),
+        ];
+
+        Self::new(
+            rules
+                .into_iter()
+                .map(|rule| match rule.date {
+                    DateRule::Fixed { .. } => rule.substitute(Substitute::NearestWeekday),
+                    _ => rule,
+                })
+                .collect(),
+        )
+        .substitute_name("{} (observed)")
+    }
+
+    /// Bank holidays of the United Kingdom (England and Wales)
+    ///
+    /// The current rules (since 1978), with the bank holidays moved or added by royal
+    /// proclamation since 1995. Later one-off changes are not known until they are added.
+    pub fn uk() -> Self {
+        use Weekday::*;
+
+        Self::new(vec![
+            HolidayRule::fixed("New Year's Day", 1, 1).substitute(Substitute::NextWeekday),
+            HolidayRule::new("Good Friday", DateRule::Easter(-2)),
+            HolidayRule::new("Easter Monday", DateRule::Easter(1)),
+            HolidayRule::nth("Early May bank holiday", 5, Mon, 1)
+                .years(1978..=i32::MAX)
+                .moved(1995, 5, 8)
+                .moved(2020, 5, 8),
+            HolidayRule::nth("Spring bank holiday", 5, Mon, -1)
+                .moved(2002, 6, 4)
+                .moved(2012, 6, 4)
+                .moved(2022, 6, 2),
+            HolidayRule::nth("Summer bank holiday", 8, Mon, -1),
+            HolidayRule::fixed("Christmas Day", 12, 25).substitute(Substitute::NextWeekday),
+            HolidayRule::fixed("Boxing Day", 12, 26).substitute(Substitute::NextWeekday),
+            // one-off bank holidays
+            HolidayRule::fixed("Millennium bank holiday", 12, 31).years(1999..=1999),
+            HolidayRule::fixed("Golden Jubilee bank holiday", 6, 3).years(2002..=2002),
+            HolidayRule::fixed("Royal wedding bank holiday", 4, 29).years(2011..=2011),
+            HolidayRule::fixed("Diamond Jubilee bank holiday", 6, 5).years(2012..=2012),
+            HolidayRule::fixed("Platinum Jubilee bank holiday", 6, 3).years(2022..=2022),
+            HolidayRule::fixed("State Funeral of Queen Elizabeth II", 9, 19).years(2022..=2022),
+            HolidayRule::fixed("Coronation bank holiday", 5, 8).years(2023..=2023),
+        ])
+        .substitute_name("{} (substitute day)")
+    }
+
+    /// National holidays of Japan (rules since 2000)
+    ///
+    /// The equinox days are computed for 1980-2099 only and left out in other years; they
+    /// are officially announced a year ahead, so later years may differ.
+    pub fn japan() -> Self {
+        use Weekday::*;
+
+        let rules = vec![
+            HolidayRule::fixed("New Year's Day", 1, 1),
+            HolidayRule::nth("Coming of Age Day", 1, Mon, 2),
+            HolidayRule::fixed("National Foundation Day", 2, 11),
+            HolidayRule::fixed("Emperor's Birthday", 2, 23).years(2020..=i32::MAX),
+            HolidayRule::new("Vernal Equinox Day", DateRule::VernalEquinox),
+            HolidayRule::fixed("Greenery Day", 4, 29).years(i32::MIN..=2006),
+            HolidayRule::fixed("Showa Day", 4, 29).years(2007..=i32::MAX),
+            HolidayRule::fixed("Constitution Memorial Day", 5, 3),
+            HolidayRule::fixed("Greenery Day", 5, 4).years(2007..=i32::MAX),
+            HolidayRule::fixed("Children's Day", 5, 5),
+            HolidayRule::fixed("Marine Day", 7, 20).years(1996..=2002),
+            HolidayRule::nth("Marine Day", 7, Mon, 3).years(2003..=2019),
+            HolidayRule::fixed("Marine Day", 7, 23).years(2020..=2020),
+            HolidayRule::fixed("Marine Day", 7, 22).years(2021..=2021),
+            HolidayRule::nth("Marine Day", 7, Mon, 3).years(2022..=i32::MAX),
+            HolidayRule::fixed("Mountain Day", 8, 11).years(2016..=2019),
+            HolidayRule::fixed("Mountain Day", 8, 10).years(2020..=2020),
+            HolidayRule::fixed("Mountain Day", 8, 8).years(2021..=2021),
+            HolidayRule::fixed("Mountain Day", 8, 11).years(2022..=i32::MAX),
+            HolidayRule::fixed("Respect for the Aged Day", 9, 15).years(i32::MIN..=2002),
+            HolidayRule::nth("Respect for the Aged Day", 9, Mon, 3).years(2003..=i32::MAX),
+            HolidayRule::new("Autumnal Equinox Day", DateRule::AutumnalEquinox),
+            HolidayRule::nth("Health and Sports Day", 10, Mon, 2).years(2000..=2019),
+            HolidayRule::fixed("Sports Day", 7, 24).years(2020..=2020),
+            HolidayRule::fixed("Sports Day", 7, 23).years(2021..=2021),
+            HolidayRule::nth("Sports Day", 10, Mon, 2).years(2022..=i32::MAX),
+            HolidayRule::fixed("Culture Day", 11, 3),
+            HolidayRule::fixed("Labour Thanksgiving Day", 11, 23),
+            HolidayRule::fixed("Emperor's Birthday", 12, 23).years(1989..=2018),
+            // April 30 and May 2 are citizens' holidays between these
+            HolidayRule::fixed("Enthronement Day", 5, 1).years(2019..=2019),
+            HolidayRule::fixed("Enthronement Ceremony Day", 10, 22).years(2019..=2019),
+        ];
+
+        Self::new(
+            rules
+                .into_iter()
+                .map(|rule| rule.substitute(Substitute::NextNonHoliday))
+                .collect(),
+        )
+        .substitute_name("Substitute Holiday")
+        .bridge_name("Citizens' Holiday")
+    }
+
+    /// Nationwide public holidays of Germany
+    pub fn germany() -> Self {
+        Self::new(vec![
+            HolidayRule::fixed("New Year's Day", 1, 1),
+            HolidayRule::new("Good Friday", DateRule::Easter(-2)),
+            HolidayRule::new("Easter Monday", DateRule::Easter(1)),
+            HolidayRule::fixed("Labour Day", 5, 1),
+            HolidayRule::new("Ascension Day", DateRule::Easter(39)),
+            HolidayRule::new("Whit Monday", DateRule::Easter(50)),
+            HolidayRule::fixed("German Unity Day", 10, 3).years(1990..=i32::MAX),
+            HolidayRule::fixed("Christmas Day", 12, 25),
+            HolidayRule::fixed("Second Day of Christmas", 12, 26),
+        ])
+    }
+
+    /// Holidays generated by the rules of `year`, which may fall in the neighboring years
+    fn holidays_of_rules(&self, year: i32) -> Vec<Holiday> {
+        let mut holidays: Vec<(Holiday, Substitute)> = self
+            .rules
+            .iter()
+            .filter(|rule| rule.years.contains(&year))
+            .filter_map(|rule| {
+                let date = rule.date(year)?;
+                Some((Holiday::new(date, &rule.name), rule.substitute))
+            })
+            .collect();
+        holidays.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut taken: BTreeSet<NaiveDate> = holidays.iter().map(|(h, _)| h.date).collect();
+        let mut substitutes = vec![];
+
+        for (holiday, substitute) in &holidays {
+            let weekday = holiday.date.weekday();
+
+            let date = match substitute {
+                Substitute::None => None,
+                Substitute::NearestWeekday => match weekday {
+                    Weekday::Sat => holiday.date.pred_opt(),
+                    Weekday::Sun => holiday.date.succ_opt(),
+                    _ => None,
+                },
+                Substitute::NextWeekday if matches!(weekday, Weekday::Sat | Weekday::Sun) => {
+                    holiday.date.iter_days().skip(1).find(|d| {
+                        !matches!(d.weekday(), Weekday::Sat | Weekday::Sun) && !taken.contains(d)
+                    })
+                }
+                Substitute::NextNonHoliday if weekday == Weekday::Sun => holiday
+                    .date
+                    .iter_days()
+                    .skip(1)
+                    .find(|d| !taken.contains(d)),
+                _ => None,
+            };
+
+            if let Some(date) = date {
+                taken.insert(date);
+                substitutes.push(Holiday::new(
+                    date,
+                    self.substitute_name.replace("{}", &holiday.name),
+                ));
+            }
+        }
+
+        if let Some(bridge_name) = &self.bridge_name {
+            for holiday in &holidays {
+                let Some(next) = holiday.0.date.succ_opt() else {
+                    continue;
+                };
+                let Some(after_next) = next.succ_opt() else {
+                    continue;
+                };
+
+                if next.weekday() != Weekday::Sun
+                    && !taken.contains(&next)
+                    && holidays.iter().any(|(h, _)| h.date == after_next)
+                {
+                    substitutes.push(Holiday::new(next, bridge_name));
+                }
+            }
+        }
+
+        let mut holidays: Vec<Holiday> = holidays.into_iter().map(|(h, _)| h).collect();
+        holidays.extend(substitutes);
+        holidays.sort();
+
+        holidays
+    }
+}
+
+impl HolidayProvider for RuleBasedHolidays {
+    fn holidays(&self, year: i32) -> Vec<Holiday> {
+        let years = year.checked_sub(1).unwrap_or(year)..=year.checked_add(1).unwrap_or(year);
+        let mut holidays: Vec<Holiday> = years
+            .flat_map(|y| self.holidays_of_rules(y))
+            .filter(|h| h.date.year() == year)
+            .collect();
+        holidays.sort();
+        holidays.dedup();
+
+        holidays
+    }
+}
+
+/// Easter Sunday (anonymous Gregorian algorithm), `None` outside the years of `NaiveDate`
+pub fn easter(year: i32) -> Option<NaiveDate> {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+}
+
+fn nth_weekday(year: i32, month: u32, weekday: Weekday, n: i32) -> Option<NaiveDate> {
+    if n > 0 {
+        NaiveDate::from_weekday_of_month_opt(year, month, weekday, n as u8)
+    } else {
+        let (next_year, next_month) = if month == 12 {
+            (year.checked_add(1)?, 1)
+        } else {
+            (year, month + 1)
+        };
+        let last_day = NaiveDate::from_ymd_opt(next_year, next_month, 1)?.pred_opt()?;
+        let back =
+            (last_day.weekday().num_days_from_monday() + 7 - weekday.num_days_from_monday()) % 7;
+
+        let date = last_day - Duration::days(back as i64 + 7 * (-n - 1) as i64);
+        (date.month() == month).then_some(date)
+    }
+}
+
+fn equinox(year: i32, base: f64, month: u32) -> Option<NaiveDate> {
+    if !(1980..=2099).contains(&year) {
+        return None;
+    }
+
+    let t = (year - 1980) as f64;
+    let day = (base + 0.242194 * t - (t / 4.0).floor()).floor();
+
+    NaiveDate::from_ymd_opt(year, month, day as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::n_date;
+
+    use super::*;
+
+    fn dates(provider: &dyn HolidayProvider, year: i32) -> Vec<(NaiveDate, String)> {
+        provider
+            .holidays(year)
+            .into_iter()
+            .map(|h| (h.date, h.name))
+            .collect()
+    }
+
+    #[test]
+    fn test_easter() {
+        assert_eq!(easter(2024), Some(n_date!(2024, 3, 31)));
+        assert_eq!(easter(2025), Some(n_date!(2025, 4, 20)));
+        assert_eq!(easter(2038), Some(n_date!(2038, 4, 25)));
+        assert_eq!(easter(i32::MAX), None);
+
+        assert!(RuleBasedHolidays::uk().holidays(i32::MAX).is_empty());
+        assert!(RuleBasedHolidays::uk().holidays(i32::MIN).is_empty());
+    }
+
+    #[test]
+    fn test_us() {
+        let holidays = dates(&RuleBasedHolidays::us(), 2021);
+
+        assert!(holidays.contains(&(n_date!(2021, 12, 31), "New Year's Day (observed)".into())));
+        assert!(holidays.contains(&(n_date!(2021, 11, 25), "Thanksgiving Day".into())));
+        assert!(holidays.contains(&(n_date!(2021, 5, 31), "Memorial Day".into())));
+        assert!(holidays.contains(&(n_date!(2021, 7, 5), "Independence Day (observed)".into())));
+    }
+
+    #[test]
+    fn test_uk() {
+        let holidays = dates(&RuleBasedHolidays::uk(), 2022);
+
+        assert!(holidays.contains(&(
+            n_date!(2022, 1, 3),
+            "New Year's Day (substitute day)".into()
+        )));
+        assert!(holidays.contains(&(
+            n_date!(2022, 12, 27),
+            "Christmas Day (substitute day)".into()
+        )));
+        assert!(holidays.contains(&(n_date!(2022, 4, 15), "Good Friday".into())));
+        // moved for the Platinum Jubilee
+        assert!(holidays.contains(&(n_date!(2022, 6, 2), "Spring bank holiday".into())));
+        assert!(!holidays
+            .iter()
+            .any(|(date, _)| *date == n_date!(2022, 5, 30)));
+        assert_eq!(holidays.len(), 12);
+
+        let holidays = dates(&RuleBasedHolidays::uk(), 2020);
+        assert!(holidays.contains(&(n_date!(2020, 5, 8), "Early May bank holiday".into())));
+        assert!(!holidays
+            .iter()
+            .any(|(date, _)| *date == n_date!(2020, 5, 4)));
+
+        let holidays = dates(&RuleBasedHolidays::uk(), 2021);
+        assert!(holidays.contains(&(
+            n_date!(2021, 12, 27),
+            "Christmas Day (substitute day)".into()
+        )));
+        assert!(holidays.contains(&(n_date!(2021, 12, 28), "Boxing Day (substitute day)".into())));
+    }
+
+    #[test]
+    fn test_japan() {
+        let holidays = dates(&RuleBasedHolidays::japan(), 2019);
+        let golden_week: Vec<NaiveDate> = holidays
+            .iter()
+            .map(|(date, _)| *date)
+            .filter(|date| date.month() == 5 && date.day() <= 6 || *date == n_date!(2019, 4, 30))
+            .collect();
+        assert_eq!(golden_week.len(), 7);
+        assert!(holidays.contains(&(n_date!(2019, 10, 22), "Enthronement Ceremony Day".into())));
+
+        let holidays = dates(&RuleBasedHolidays::japan(), 2026);
+
+        assert!(holidays.contains(&(n_date!(2026, 3, 20), "Vernal Equinox Day".into())));
+        assert!(holidays.contains(&(n_date!(2026, 5, 6), "Substitute Holiday".into())));
+        assert!(holidays.contains(&(n_date!(2026, 9, 22), "Citizens' Holiday".into())));
+        assert!(holidays.contains(&(n_date!(2026, 9, 23), "Autumnal Equinox Day".into())));
+        assert_eq!(holidays.len(), 18);
+    }
+
+    #[test]
+    fn test_germany() {
+        let holidays = dates(&RuleBasedHolidays::germany(), 2024);
+
+        assert_eq!(holidays.len(), 9);
+        assert!(holidays.contains(&(n_date!(2024, 5, 9), "Ascension Day".into())));
+        assert!(holidays.contains(&(n_date!(2024, 5, 20), "Whit Monday".into())));
+    }
+}
@@ -0,0 +1,1348 @@
+use std::{fmt::Display, ops::RangeInclusive, str::FromStr};
+
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+
+use crate::{
+    recurrence::parse_date, zone::parse_offset, Calendar, Category, Daylight, Error, Frequency,
+    RRule, Recurrence, Transition, Zone, ZoneRules,
+};
+
+/// VEVENT of an iCalendar file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    /// UID as read, or derived from the content when the calendar is written
+    pub uid: Option<String>,
+    pub summary: Option<String>,
+    pub description: Option<String>,
+    /// CATEGORIES
+    pub categories: Vec<String>,
+    /// First day, in `display_zone`
+    pub start: NaiveDate,
+    /// Last day (inclusive), in `display_zone`
+    pub end: NaiveDate,
+    /// Recurrence of the start, expanded in the time zone of the event
+    pub recurrence: Option<Recurrence>,
+    /// Start and end times of timed events, as written in `zone`
+    pub times: Option<(NaiveDateTime, NaiveDateTime)>,
+    /// Time zone of `times` (`None` for all-day events and floating times)
+    pub zone: Option<Zone>,
+    /// Time zone of the dates and times of the occurrences (the one given to
+    /// [`ICalendar::parse`])
+    pub display_zone: Zone,
+}
+
+impl Event {
+    /// Days of the event (and its recurrences) in the range
+    pub fn dates(&self, range: RangeInclusive<NaiveDate>) -> Vec<NaiveDate> {
+        let mut dates: Vec<NaiveDate> = match self.times {
+            Some(_) => self
+                .times_between(range.clone())
+                .into_iter()
+                .flat_map(|(start, end)| {
+                    start
+                        .date()
+                        .iter_days()
+                        .take_while(move |d| *d <= last_day(start, end))
+                })
+                .collect(),
+            None => {
+                let length = self.end - self.start;
+                let from = range
+                    .start()
+                    .checked_sub_signed(length)
+                    .unwrap_or(NaiveDate::MIN);
+
+                self.starts(from..=*range.end())
+                    .into_iter()
+                    .flat_map(|start| {
+                        let end = start.checked_add_signed(length).unwrap_or(NaiveDate::MAX);
+                        start.iter_days().take_while(move |d| *d <= end)
+                    })
+                    .collect()
+            }
+        };
+        dates.retain(|d| range.contains(d));
+        dates.sort();
+        dates.dedup();
+
+        dates
+    }
+
+    /// Start and end times of the occurrences overlapping the range, in `display_zone`
+    /// (empty for all-day events)
+    ///
+    /// The recurrence is expanded in the time zone of the event first, so that `BYDAY`
+    /// and `UNTIL` apply to its own dates.
+    pub fn times_between(
+        &self,
+        range: RangeInclusive<NaiveDate>,
+    ) -> Vec<(NaiveDateTime, NaiveDateTime)> {
+        let Some((start, end)) = self.times else {
+            return vec![];
+        };
+        let length = end - start;
+        // the offset between two zones is less than two days
+        let margin = Duration::days(2) + length;
+        let from = range
+            .start()
+            .checked_sub_signed(margin)
+            .unwrap_or(NaiveDate::MIN);
+        let to = range
+            .end()
+            .checked_add_signed(Duration::days(2))
+            .unwrap_or(NaiveDate::MAX);
+
+        self.starts(from..=to)
+            .into_iter()
+            .map(|date| {
+                let start = date.and_time(start.time());
+                (self.displayed(start), self.displayed(start + length))
+            })
+            .filter(|(start, end)| {
+                start.date() <= *range.end() && last_day(*start, *end) >= *range.start()
+            })
+            .collect()
+    }
+
+    /// First days of the occurrences in the range, in the time zone of the event
+    fn starts(&self, range: RangeInclusive<NaiveDate>) -> Vec<NaiveDate> {
+        let first = self.times.map_or(self.start, |(start, _)| start.date());
+
+        match &self.recurrence {
+            Some(recurrence) => recurrence.between(range),
+            None if range.contains(&first) => vec![first],
+            None => vec![],
+        }
+    }
+
+    /// UID made from the dates, the categories and the summary, so that the same event
+    /// gets the same UID every time it is written
+    fn derived_uid(&self, domain: &str) -> String {
+        let (start, end) = match self.times {
+            Some((start, end)) => (
+                start.format("%Y%m%dT%H%M%S").to_string(),
+                end.format("%Y%m%dT%H%M%S").to_string(),
+            ),
+            None => (
+                self.start.format("%Y%m%d").to_string(),
+                self.end.format("%Y%m%d").to_string(),
+            ),
+        };
+        let categories: String = self
+            .categories
+            .join("+")
+            .chars()
+            .map(|c| match c {
+                'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '+' => c,
+                _ => '_',
+            })
+            .collect();
+        let hash = fnv1a(self.summary.as_deref().unwrap_or_default());
+
+        let uid = format!("{}-{}-{}-{:016x}", start, end, categories, hash);
+        if domain.is_empty() {
+            uid
+        } else {
+            format!("{}@{}", uid, domain)
+        }
+    }
+
+    /// Time of the event in `display_zone`
+    fn displayed(&self, time: NaiveDateTime) -> NaiveDateTime {
+        match &self.zone {
+            Some(zone) => self.display_zone.local(zone.to_utc(time)),
+            None => time,
+        }
+    }
+}
+
+/// 64-bit FNV-1a, which unlike `DefaultHasher` is the same on every platform and release
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf29ce484222325, |hash, b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Last day of a timed event (one ending at midnight does not occupy the next day)
+fn last_day(start: NaiveDateTime, end: NaiveDateTime) -> NaiveDate {
+    if end > start && end.time() == NaiveTime::MIN {
+        end.date() - Duration::days(1)
+    } else {
+        end.date()
+    }
+}
+
+/// Events read from an iCalendar (.ics) file
+///
+/// All-day events cover their dates as written. Timed events are expanded in their
+/// own time zone and then mapped to dates in the [`Zone`] given to [`ICalendar::parse`].
+/// UTC times (`...Z`) and `TZID`s are converted, floating times are taken as they are
+/// written. A `TZID` is resolved by [`Zone::from_tzid`], or else by the `VTIMEZONE` of the
+/// same name in the file.
+///
+/// Events which cannot be read (e.g. with an unknown `TZID` or an unsupported `RRULE`)
+/// are left out and listed by [`ICalendar::skipped`].
+///
+/// ```
+/// use text_calendar::{Calendar, ICalendar, MonthCalendar};
+///
+/// let ics = "BEGIN:VCALENDAR\r\n\
+///            BEGIN:VEVENT\r\n\
+///            DTSTART;VALUE=DATE:20240610\r\n\
+///            DTEND;VALUE=DATE:20240613\r\n\
+///            SUMMARY:Offsite\r\n\
+///            END:VEVENT\r\n\
+///            END:VCALENDAR\r\n";
+///
+/// let mut calendar = MonthCalendar::from_ym(2024, 6).unwrap();
+/// ics.parse::<ICalendar>().unwrap().mark(&mut calendar);
+/// ```
+///
+/// Two calendars are equal when their events are, whatever their DTSTAMPs.
+#[derive(Debug, Clone, Default)]
+pub struct ICalendar {
+    events: Vec<Event>,
+    /// Events left out by `parse`
+    skipped: Vec<SkippedEvent>,
+    /// DTSTAMP of the written events
+    timestamp: DateTime<Utc>,
+    /// Domain of the derived UIDs
+    domain: String,
+}
+
+/// How [`ICalendar::from_calendar`] turns marked days into events
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EventSpan {
+    /// One event per day
+    #[default]
+    Day,
+    /// Contiguous days with the same categories as one multi-day event
+    Contiguous,
+}
+
+impl ICalendar {
+    /// Parse with timed events mapped to dates in `zone` (a `FixedOffset`, or a
+    /// `chrono_tz::Tz` with the `tz` feature)
+    ///
+    /// Only a file which is not made of components (`BEGIN` and `END` do not match, or a
+    /// line is not `NAME:VALUE` outside of an event) is an error.
+    pub fn parse(s: &str, zone: impl Into<Zone>) -> Result<Self, Error> {
+        let zone = zone.into();
+        let root = Component::parse(s)?;
+
+        let zones: Vec<Zone> = root
+            .find("VTIMEZONE")
+            .into_iter()
+            .filter_map(|component| parse_vtimezone(component).ok())
+            .map(Zone::from)
+            .collect();
+
+        let mut events = vec![];
+        let mut skipped = vec![];
+        for component in root.find("VEVENT") {
+            let event = match &component.error {
+                Some(error) => Err(error.clone()),
+                None => parse_event(&component.properties, &zones, &zone),
+            };
+            match event {
+                Ok(event) => events.push(event),
+                Err(error) => skipped.push(SkippedEvent {
+                    uid: component.property("UID").map(|uid| uid.trim().to_string()),
+                    summary: component.property("SUMMARY").map(unescape),
+                    error,
+                }),
+            }
+        }
+
+        Ok(Self {
+            events,
+            skipped,
+            timestamp: Utc::now(),
+            domain: String::new(),
+        })
+    }
+
+    /// Events for the marked days of `calendar`
+    ///
+    /// The summary and `CATEGORIES` are the category labels of the day, the description
+    /// lists its annotations and holiday names. `span` tells whether contiguous days with
+    /// the same categories become one multi-day event.
+    ///
+    /// UIDs are made from the content of the events and `domain` (e.g. `example.com`), so
+    /// writing the same marks again updates the events instead of duplicating them.
+    /// DTSTAMP is the current time (see `set_timestamp`).
+    pub fn from_calendar<C: Calendar + ?Sized>(
+        calendar: &C,
+        span: EventSpan,
+        domain: &str,
+    ) -> Self {
+        let mut ics = Self {
+            events: vec![],
+            skipped: vec![],
+            timestamp: Utc::now(),
+            domain: domain.to_string(),
+        };
+        let Some(range) = calendar.date_range() else {
+            return ics;
+        };
+        let holidays = calendar.holidays();
+
+        // (event, lines of the description)
+        let mut events: Vec<(Event, Vec<String>)> = vec![];
+
+        for date in range.start().iter_days().take_while(|d| d <= range.end()) {
+            let categories: Vec<String> = calendar
+                .categories_of(date)
+                .iter()
+                .map(|c| c.name().to_string())
+                .collect();
+            if categories.is_empty() {
+                continue;
+            }
+
+            let mut notes = calendar.annotations(date);
+            notes.extend(
+                holidays
+                    .iter()
+                    .filter(|h| h.date == date)
+                    .map(|h| h.name.clone()),
+            );
+
+            if let Some((last, last_notes)) = events.last_mut().filter(|(last, _)| {
+                span == EventSpan::Contiguous
+                    && last.end.succ_opt() == Some(date)
+                    && last.categories == categories
+            }) {
+                last.end = date;
+                for note in notes {
+                    if !last_notes.contains(&note) {
+                        last_notes.push(note);
+                    }
+                }
+                continue;
+            }
+
+            let event = Event {
+                uid: None,
+                summary: Some(categories.join(", ")),
+                description: None,
+                categories,
+                start: date,
+                end: date,
+                recurrence: None,
+                times: None,
+                zone: None,
+                display_zone: Zone::utc(),
+            };
+            events.push((event, notes));
+        }
+
+        ics.events = events
+            .into_iter()
+            .map(|(mut event, notes)| {
+                if !notes.is_empty() {
+                    event.description = Some(notes.join("\n"));
+                }
+                event.uid = Some(event.derived_uid(domain));
+                event
+            })
+            .collect();
+
+        ics
+    }
+
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    /// Events left out by [`ICalendar::parse`], with the reason
+    pub fn skipped(&self) -> &[SkippedEvent] {
+        &self.skipped
+    }
+
+    /// DTSTAMP of the written events (default: the time the calendar was made)
+    pub fn set_timestamp(&mut self, timestamp: DateTime<Utc>) {
+        self.timestamp = timestamp;
+    }
+
+    /// Domain of the UIDs derived for events read without one
+    pub fn set_domain(&mut self, domain: &str) {
+        self.domain = domain.to_string();
+    }
+
+    /// Mark the events in the displayed range of the calendar and annotate them with their summaries
+    pub fn mark<C: Calendar + ?Sized>(&self, calendar: &mut C) {
+        self.apply(calendar, |calendar, date| calendar.mark(date));
+    }
+
+    /// Same as [`ICalendar::mark`], with a category
+    pub fn mark_category<C: Calendar + ?Sized>(&self, calendar: &mut C, category: &Category) {
+        self.apply(calendar, |calendar, date| {
+            calendar.mark_category(date, category)
+        });
+    }
+
+    fn apply<C: Calendar + ?Sized>(&self, calendar: &mut C, mark: impl Fn(&mut C, NaiveDate)) {
+        let Some(range) = calendar.date_range() else {
+            return;
+        };
+
+        for event in &self.events {
+            for date in event.dates(range.clone()) {
+                mark(calendar, date);
+                if let Some(summary) = &event.summary {
+                    calendar.annotate(date, summary);
+                }
+            }
+        }
+    }
+}
+
+impl PartialEq for ICalendar {
+    fn eq(&self, other: &Self) -> bool {
+        self.events == other.events && self.skipped == other.skipped && self.domain == other.domain
+    }
+}
+
+impl Eq for ICalendar {}
+
+/// VEVENT which [`ICalendar::parse`] could not read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedEvent {
+    pub uid: Option<String>,
+    pub summary: Option<String>,
+    pub error: Error,
+}
+
+/// `BEGIN:NAME` ... `END:NAME`, with its properties and the components inside
+#[derive(Debug, Default)]
+struct Component {
+    name: String,
+    properties: Vec<(String, String, String)>,
+    components: Vec<Component>,
+    /// First line which could not be split (the component cannot be read)
+    error: Option<Error>,
+}
+
+impl Component {
+    /// Components of a file, under an unnamed root
+    fn parse(s: &str) -> Result<Self, Error> {
+        let mut stack = vec![Component::default()];
+
+        for line in unfold(s) {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let depth = stack.len();
+            let current = stack.last_mut().unwrap();
+            let (name, params, value) = match split_line(&line) {
+                Ok(parts) => parts,
+                // a broken line only makes its component unreadable
+                Err(error) if depth > 1 => {
+                    current.error.get_or_insert(error);
+                    continue;
+                }
+                Err(error) => return Err(error),
+            };
+
+            match name.as_str() {
+                "BEGIN" => stack.push(Component {
+                    name: value.trim().to_ascii_uppercase(),
+                    ..Default::default()
+                }),
+                "END" => {
+                    let end = value.trim().to_ascii_uppercase();
+                    if depth == 1 || current.name != end {
+                        return Err(invalid(format!("END:{} without BEGIN:{}", end, end)));
+                    }
+                    let component = stack.pop().unwrap();
+                    stack.last_mut().unwrap().components.push(component);
+                }
+                _ => current.properties.push((name, params, value)),
+            }
+        }
+
+        match stack.pop() {
+            Some(root) if stack.is_empty() => Ok(root),
+            Some(component) => Err(invalid(format!("{} is not closed", component.name))),
+            None => unreachable!(),
+        }
+    }
+
+    /// Components named `name`, at any depth but not inside each other
+    fn find(&self, name: &str) -> Vec<&Component> {
+        self.components
+            .iter()
+            .flat_map(|c| {
+                if c.name == name {
+                    vec![c]
+                } else {
+                    c.find(name)
+                }
+            })
+            .collect()
+    }
+
+    /// Value of the first property named `name`
+    fn property(&self, name: &str) -> Option<&str> {
+        self.properties
+            .iter()
+            .find(|(n, _, _)| n == name)
+            .map(|(_, _, value)| value.as_str())
+    }
+}
+
+/// Zone of a VTIMEZONE, from its latest STANDARD and DAYLIGHT observances
+///
+/// Daylight saving time is kept when both observances recur yearly on an n-th weekday
+/// of a month (`BYMONTH=3;BYDAY=2SU`); otherwise the offset of the latest one is used.
+fn parse_vtimezone(component: &Component) -> Result<ZoneRules, Error> {
+    let tzid = component
+        .property("TZID")
+        .ok_or_else(|| invalid("VTIMEZONE without TZID"))?;
+
+    // (kind, start, offset, yearly change)
+    let mut observances = vec![];
+    for observance in &component.components {
+        let start = observance
+            .property("DTSTART")
+            .and_then(|value| NaiveDateTime::parse_from_str(value.trim(), "%Y%m%dT%H%M%S").ok());
+        let offset = observance
+            .property("TZOFFSETTO")
+            .and_then(|value| parse_offset(value.trim()));
+        let (Some(start), Some(offset)) = (start, offset) else {
+            return Err(invalid(format!(
+                "invalid {} of `{}`",
+                observance.name, tzid
+            )));
+        };
+
+        let transition = observance
+            .property("RRULE")
+            .and_then(|value| value.parse::<RRule>().ok())
+            .and_then(
+                |rule| match (rule.frequency, &rule.by_month[..], &rule.by_day[..]) {
+                    (Frequency::Yearly, [month], [day])
+                        if day.n.is_some() && rule.until.is_none() =>
+                    {
+                        Some(Transition {
+                            month: *month,
+                            day: *day,
+                            time: start.time(),
+                        })
+                    }
+                    _ => None,
+                },
+            );
+        observances.push((observance.name.as_str(), start, offset, transition));
+    }
+
+    let latest = |kind: &str| {
+        observances
+            .iter()
+            .filter(|(name, ..)| *name == kind)
+            .max_by_key(|(_, start, ..)| *start)
+    };
+    let rules = match (latest("STANDARD"), latest("DAYLIGHT")) {
+        (Some((_, _, standard, Some(end))), Some((_, _, daylight, Some(start)))) => ZoneRules::new(
+            tzid.trim(),
+            *standard,
+            Some(Daylight {
+                offset: *daylight,
+                start: *start,
+                end: *end,
+            }),
+        ),
+        _ => {
+            let (_, _, offset, _) = observances
+                .iter()
+                .max_by_key(|(_, start, ..)| *start)
+                .ok_or_else(|| invalid(format!("VTIMEZONE `{}` without observances", tzid)))?;
+            ZoneRules::new(tzid.trim(), *offset, None)
+        }
+    };
+
+    Ok(rules)
+}
+
+impl FromStr for ICalendar {
+    type Err = Error;
+
+    /// Parse with timed events mapped to dates in UTC
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, Zone::utc())
+    }
+}
+
+impl Display for ICalendar {
+    /// Write an .ics file
+    ///
+    /// Timed events are written in UTC, with the `TZID` of their zone, or in floating time
+    /// as they were read. Every zone but UTC gets a `VTIMEZONE`, with the rules of the year
+    /// of its first event (see [`Zone::rules`]).
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let timestamp = self.timestamp.format("%Y%m%dT%H%M%SZ");
+
+        write_line(f, "BEGIN:VCALENDAR")?;
+        write_line(f, "VERSION:2.0")?;
+        write_line(f, "PRODID:-//text-calendar//EN")?;
+
+        let mut zones: Vec<ZoneRules> = vec![];
+        for event in &self.events {
+            match &event.zone {
+                Some(zone) if !zone.is_utc() && zones.iter().all(|z| z.tzid() != zone.tzid()) => {
+                    zones.push(zone.rules(event.start.year()));
+                }
+                _ => {}
+            }
+        }
+        for rules in &zones {
+            write_vtimezone(f, rules)?;
+        }
+
+        for event in &self.events {
+            let uid = match &event.uid {
+                Some(uid) => uid.clone(),
+                None => event.derived_uid(&self.domain),
+            };
+            write_line(f, "BEGIN:VEVENT")?;
+            write_line(f, &format!("UID:{}", uid))?;
+            write_line(f, &format!("DTSTAMP:{}", timestamp))?;
+            match event.times {
+                Some((start, end)) => {
+                    write_line(f, &date_time("DTSTART", start, event.zone.as_ref()))?;
+                    write_line(f, &date_time("DTEND", end, event.zone.as_ref()))?;
+                }
+                None => {
+                    write_line(
+                        f,
+                        &format!("DTSTART;VALUE=DATE:{}", event.start.format("%Y%m%d")),
+                    )?;
+                    let end = event.end.succ_opt().unwrap_or(event.end);
+                    write_line(f, &format!("DTEND;VALUE=DATE:{}", end.format("%Y%m%d")))?;
+                }
+            }
+
+            if let Some(recurrence) = &event.recurrence {
+                let mut rule = recurrence.rule().clone();
+                let until = rule.until.take();
+
+                match event.times {
+                    Some((start, _)) => {
+                        let until = until.map(|until| {
+                            // UNTIL of a DATE-TIME start is a DATE-TIME, in UTC unless floating
+                            let last = until.and_hms_opt(23, 59, 59).unwrap();
+                            match &event.zone {
+                                Some(zone) => {
+                                    format!(";UNTIL={}Z", zone.to_utc(last).format("%Y%m%dT%H%M%S"))
+                                }
+                                None => format!(";UNTIL={}", last.format("%Y%m%dT%H%M%S")),
+                            }
+                        });
+                        write_line(f, &format!("RRULE:{}{}", rule, until.unwrap_or_default()))?;
+                        for date in recurrence.exdates() {
+                            write_line(
+                                f,
+                                &date_time(
+                                    "EXDATE",
+                                    date.and_time(start.time()),
+                                    event.zone.as_ref(),
+                                ),
+                            )?;
+                        }
+                    }
+                    None => {
+                        let until = until.map(|until| format!(";UNTIL={}", until.format("%Y%m%d")));
+                        write_line(f, &format!("RRULE:{}{}", rule, until.unwrap_or_default()))?;
+                        for date in recurrence.exdates() {
+                            write_line(f, &format!("EXDATE;VALUE=DATE:{}", date.format("%Y%m%d")))?;
+                        }
+                    }
+                }
+            }
+            if let Some(summary) = &event.summary {
+                write_line(f, &format!("SUMMARY:{}", escape(summary)))?;
+            }
+            if let Some(description) = &event.description {
+                write_line(f, &format!("DESCRIPTION:{}", escape(description)))?;
+            }
+            if !event.categories.is_empty() {
+                let categories: Vec<String> = event.categories.iter().map(|c| escape(c)).collect();
+                write_line(f, &format!("CATEGORIES:{}", categories.join(",")))?;
+            }
+
+            write_line(f, "END:VEVENT")?;
+        }
+
+        write_line(f, "END:VCALENDAR")
+    }
+}
+
+/// VTIMEZONE with a STANDARD observance, and a DAYLIGHT one if there is daylight saving time
+fn write_vtimezone(f: &mut std::fmt::Formatter<'_>, rules: &ZoneRules) -> std::fmt::Result {
+    let offset = |offset: FixedOffset| offset.to_string().replace(':', "");
+
+    write_line(f, "BEGIN:VTIMEZONE")?;
+    write_line(f, &format!("TZID:{}", rules.tzid()))?;
+    let observances = match rules.daylight() {
+        Some(daylight) => vec![
+            (
+                "STANDARD",
+                daylight.offset,
+                rules.standard(),
+                Some(daylight.end),
+            ),
+            (
+                "DAYLIGHT",
+                rules.standard(),
+                daylight.offset,
+                Some(daylight.start),
+            ),
+        ],
+        None => vec![("STANDARD", rules.standard(), rules.standard(), None)],
+    };
+    for (kind, from, to, transition) in observances {
+        write_line(f, &format!("BEGIN:{}", kind))?;
+        // the first change is in 1970, as is customary
+        let start = transition
+            .and_then(|t| Some(t.date(1970)?.and_time(t.time)))
+            .unwrap_or_default();
+        write_line(f, &format!("DTSTART:{}", start.format("%Y%m%dT%H%M%S")))?;
+        if let Some(transition) = transition {
+            let mut rule = RRule::new(Frequency::Yearly);
+            rule.by_month = vec![transition.month];
+            rule.by_day = vec![transition.day];
+            write_line(f, &format!("RRULE:{}", rule))?;
+        }
+        write_line(f, &format!("TZOFFSETFROM:{}", offset(from)))?;
+        write_line(f, &format!("TZOFFSETTO:{}", offset(to)))?;
+        write_line(f, &format!("END:{}", kind))?;
+    }
+
+    write_line(f, "END:VTIMEZONE")
+}
+
+/// `NAME:...Z` in UTC, `NAME;TZID=...:...` in another zone and `NAME:...` in floating time
+fn date_time(name: &str, time: NaiveDateTime, zone: Option<&Zone>) -> String {
+    let time = time.format("%Y%m%dT%H%M%S");
+
+    match zone {
+        Some(zone) if zone.is_utc() => format!("{}:{}Z", name, time),
+        Some(zone) => format!("{};TZID={}:{}", name, zone.tzid(), time),
+        None => format!("{}:{}", name, time),
+    }
+}
+
+/// Write a content line folded at 75 octets, ending with CRLF
+fn write_line(f: &mut std::fmt::Formatter<'_>, line: &str) -> std::fmt::Result {
+    let mut octets = 0;
+
+    for c in line.chars() {
+        if octets + c.len_utf8() > 75 {
+            write!(f, "\r\n ")?;
+            octets = 1;
+        }
+        write!(f, "{}", c)?;
+        octets += c.len_utf8();
+    }
+
+    write!(f, "\r\n")
+}
+
+/// Escape a TEXT value
+fn escape(text: &str) -> String {
+    let mut escaped = String::new();
+
+    for c in text.chars() {
+        match c {
+            '\\' | ';' | ',' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' => escaped.push_str("\\n"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Split a list value at unescaped commas
+fn split_list(value: &str) -> impl Iterator<Item = String> + '_ {
+    let mut escaped = false;
+
+    value
+        .split(move |c| {
+            let split = c == ',' && !escaped;
+            escaped = c == '\\' && !escaped;
+            split
+        })
+        .map(str::to_string)
+}
+
+fn invalid(message: impl Into<String>) -> Error {
+    Error::InvalidICalendar(message.into())
+}
+
+/// Join folded lines (continuation lines start with a space or a tab)
+fn unfold(s: &str) -> Vec<String> {
+    let mut lines: Vec<String> = vec![];
+
+    for line in s.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+
+    lines
+}
+
+/// `NAME;PARAMS:VALUE` (the colon may appear in quoted parameter values)
+fn split_line(line: &str) -> Result<(String, String, String), Error> {
+    let mut quoted = false;
+    let colon = line
+        .char_indices()
+        .find(|(_, c)| {
+            if *c == '"' {
+                quoted = !quoted;
+            }
+            *c == ':' && !quoted
+        })
+        .map(|(i, _)| i)
+        .ok_or_else(|| invalid(format!("`{}` is not NAME:VALUE", line)))?;
+
+    let (head, value) = (&line[..colon], &line[colon + 1..]);
+    let (name, params) = head.split_once(';').unwrap_or((head, ""));
+
+    // parameter names are case-insensitive, but values such as TZID are not
+    let params: Vec<String> = params
+        .split(';')
+        .map(|param| match param.split_once('=') {
+            Some((name, value)) => format!("{}={}", name.to_ascii_uppercase(), value),
+            None => param.to_ascii_uppercase(),
+        })
+        .collect();
+
+    Ok((
+        name.trim().to_ascii_uppercase(),
+        params.join(";"),
+        value.to_string(),
+    ))
+}
+
+/// DATE or DATE-TIME value
+#[derive(Debug, Clone)]
+enum Time {
+    Date(NaiveDate),
+    /// Local time in its zone (`None` for floating times)
+    DateTime(NaiveDateTime, Option<Zone>),
+}
+
+impl Time {
+    /// Date in `zone` (dates and floating times as written)
+    fn date_in(&self, zone: Option<&Zone>) -> NaiveDate {
+        match self {
+            Time::Date(date) => *date,
+            Time::DateTime(date_time, from) => convert(*date_time, from.as_ref(), zone).date(),
+        }
+    }
+
+    fn zone(&self) -> Option<&Zone> {
+        match self {
+            Time::Date(_) => None,
+            Time::DateTime(_, zone) => zone.as_ref(),
+        }
+    }
+}
+
+/// Local time `from` one zone `to` another (as written when either one is floating)
+fn convert(time: NaiveDateTime, from: Option<&Zone>, to: Option<&Zone>) -> NaiveDateTime {
+    match (from, to) {
+        (Some(from), Some(to)) if from != to => to.local(from.to_utc(time)),
+        _ => time,
+    }
+}
+
+/// DATE or DATE-TIME, with its `TZID` resolved by `Zone::from_tzid` or else in `zones`
+/// (the VTIMEZONEs of the file)
+fn parse_time(params: &str, value: &str, zones: &[Zone]) -> Result<Time, Error> {
+    let value = value.trim();
+    let upper = params.to_ascii_uppercase();
+    if upper.contains("VALUE=DATE") && !upper.contains("VALUE=DATE-TIME") || value.len() == 8 {
+        return parse_date(value)
+            .map(Time::Date)
+            .map_err(|_| not_a_time(value));
+    }
+
+    let (local, utc) = match value.strip_suffix(['Z', 'z']) {
+        Some(local) => (local, true),
+        None => (value, false),
+    };
+    let date_time =
+        NaiveDateTime::parse_from_str(local, "%Y%m%dT%H%M%S").map_err(|_| not_a_time(value))?;
+
+    let zone = match params.split(';').find_map(|p| p.strip_prefix("TZID=")) {
+        _ if utc => Some(Zone::utc()),
+        Some(tzid) => Some(
+            Zone::from_tzid(tzid)
+                .or_else(|| {
+                    let tzid = tzid.trim_matches('"');
+                    zones.iter().find(|zone| zone.tzid() == tzid).cloned()
+                })
+                .ok_or_else(|| {
+                    invalid(format!(
+                        "unknown TZID `{}` (no VTIMEZONE; IANA and Windows names need the `tz` feature)",
+                        tzid
+                    ))
+                })?,
+        ),
+        None => None,
+    };
+
+    Ok(Time::DateTime(date_time, zone))
+}
+
+/// Last date of `UNTIL` in the time zone of the event starting at `start`
+fn parse_until(rule: &str, start: &Time) -> Result<Option<NaiveDate>, Error> {
+    let Some(value) = rule.split(';').find_map(|part| {
+        let (key, value) = part.trim().split_once('=')?;
+        key.eq_ignore_ascii_case("UNTIL").then_some(value)
+    }) else {
+        return Ok(None);
+    };
+
+    match (parse_time("", value, &[])?, start) {
+        (Time::DateTime(until, until_zone), Time::DateTime(start, zone)) => {
+            let until = convert(until, until_zone.as_ref(), zone.as_ref());
+            // an occurrence later in the day than UNTIL is excluded
+            Ok(Some(if until.time() < start.time() {
+                until.date() - Duration::days(1)
+            } else {
+                until.date()
+            }))
+        }
+        (until, _) => Ok(Some(until.date_in(None))),
+    }
+}
+
+fn not_a_time(value: &str) -> Error {
+    invalid(format!("`{}` is not a date or date-time", value))
+}
+
+/// `P1D`, `PT1H30M`, `P1W`, ...
+fn parse_duration(value: &str) -> Result<Duration, Error> {
+    let error = || invalid(format!("`{}` is not a duration", value));
+
+    let value = value.trim();
+    let (negative, value) = match value.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let value = value.strip_prefix('P').ok_or_else(error)?;
+
+    let mut duration = Duration::zero();
+    let mut number = String::new();
+    let mut in_time = false;
+
+    for c in value.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'T' => in_time = true,
+            _ => {
+                let n: i64 = number.parse().map_err(|_| error())?;
+                number.clear();
+                duration += match (c, in_time) {
+                    ('W', false) => Duration::weeks(n),
+                    ('D', false) => Duration::days(n),
+                    ('H', true) => Duration::hours(n),
+                    ('M', true) => Duration::minutes(n),
+                    ('S', true) => Duration::seconds(n),
+                    _ => return Err(error()),
+                };
+            }
+        }
+    }
+    if !number.is_empty() {
+        return Err(error());
+    }
+
+    Ok(if negative { -duration } else { duration })
+}
+
+/// Unescape a TEXT value
+fn unescape(value: &str) -> String {
+    let mut text = String::new();
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => text.push('\n'),
+            Some(c) => text.push(c),
+            None => text.push('\\'),
+        }
+    }
+
+    text
+}
+
+fn parse_event(
+    properties: &[(String, String, String)],
+    zones: &[Zone],
+    display_zone: &Zone,
+) -> Result<Event, Error> {
+    let mut uid = None;
+    let mut summary = None;
+    let mut description = None;
+    let mut categories = vec![];
+    let mut start = None;
+    let mut end = None;
+    let mut duration = None;
+    let mut rule: Option<(RRule, &str)> = None;
+    let mut exdates = vec![];
+
+    for (name, params, value) in properties {
+        match name.as_str() {
+            "UID" => uid = Some(value.trim().to_string()),
+            "SUMMARY" => summary = Some(unescape(value)),
+            "DESCRIPTION" => description = Some(unescape(value)),
+            "CATEGORIES" => categories.extend(split_list(value).map(|c| unescape(&c))),
+            "DTSTART" => start = Some(parse_time(params, value, zones)?),
+            "DTEND" => end = Some(parse_time(params, value, zones)?),
+            "DURATION" => duration = Some(parse_duration(value)?),
+            "RRULE" => rule = Some((value.parse()?, value.as_str())),
+            "EXDATE" => {
+                for value in value.split(',') {
+                    exdates.push(parse_time(params, value, zones)?);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let start = start.ok_or_else(|| invalid("VEVENT without DTSTART"))?;
+    // times are kept in the time zone of DTSTART
+    let zone = start.zone().cloned();
+    let times = match start {
+        Time::Date(_) => None,
+        Time::DateTime(start, _) => {
+            let end = match (&end, duration) {
+                (Some(Time::DateTime(end, end_zone)), _) => {
+                    convert(*end, end_zone.as_ref(), zone.as_ref())
+                }
+                (Some(Time::Date(end)), _) => end.and_time(NaiveTime::MIN),
+                (None, Some(duration)) => start + duration,
+                (None, None) => start,
+            };
+            Some((start, end.max(start)))
+        }
+    };
+
+    let recurrence = match rule {
+        Some((mut rule, value)) => {
+            rule.until = parse_until(value, &start)?;
+            let mut recurrence = Recurrence::new(start.date_in(zone.as_ref()), rule);
+            for date in exdates {
+                recurrence.exclude(date.date_in(zone.as_ref()));
+            }
+            Some(recurrence)
+        }
+        None => None,
+    };
+
+    let mut event = Event {
+        uid,
+        summary,
+        description,
+        categories,
+        start: start.date_in(zone.as_ref()),
+        end: start.date_in(zone.as_ref()),
+        recurrence,
+        times,
+        zone,
+        display_zone: display_zone.clone(),
+    };
+
+    match times {
+        Some((start, end)) => {
+            let (start, end) = (event.displayed(start), event.displayed(end));
+            event.start = start.date();
+            event.end = last_day(start, end);
+        }
+        // the end of an all-day event is exclusive
+        None => {
+            let last_day = match (end, duration) {
+                (Some(end), _) => end.date_in(None) - Duration::days(1),
+                (None, Some(duration)) => event.start + duration - Duration::days(1),
+                (None, None) => event.start,
+            };
+            event.end = last_day.max(event.start);
+        }
+    }
+
+    Ok(event)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{FixedOffset, TimeZone, Weekday};
+
+    use crate::{n_date, BasicMarker, MonthCalendar, YearCalendar};
+
+    use super::*;
+
+    const ICS: &str = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+BEGIN:VEVENT\r
+DTSTART;VALUE=DATE:20240610\r
+DTEND;VALUE=DATE:20240613\r
+SUMMARY:Offsite\\, Kyoto\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+DTSTART:20240604T230000Z\r
+DURATION:PT1H\r
+SUMMARY:Release\r
+BEGIN:VALARM\r
+TRIGGER:-PT15M\r
+END:VALARM\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+DTSTART;TZID=UTC+0900:20240603T100000\r
+DTEND;TZID=UTC+0900:20240603T103000\r
+RRULE:FREQ=WEEKLY;BYDAY=MO\r
+EXDATE;TZID=UTC+0900:20240617T100000\r
+SUMMARY:Weekly\r
+  sync\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+
+    #[test]
+    fn test() {
+        let ics = ICalendar::parse(ICS, FixedOffset::east_opt(9 * 3600).unwrap()).unwrap();
+        assert_eq!(ics.events().len(), 3);
+
+        let mut cal = MonthCalendar::from_ym(2024, 6).unwrap();
+        ics.mark(&mut cal);
+
+        let marked: Vec<u32> = (1..=30)
+            .filter(|d| cal.is_marked(n_date!(2024, 6, *d)))
+            .collect();
+        assert_eq!(marked, [3, 5, 10, 11, 12, 24]);
+
+        assert_eq!(cal.annotations(n_date!(2024, 6, 11)), ["Offsite, Kyoto"]);
+        assert_eq!(cal.annotations(n_date!(2024, 6, 5)), ["Release"]);
+        assert_eq!(
+            cal.annotations(n_date!(2024, 6, 10)),
+            ["Offsite, Kyoto", "Weekly sync"]
+        );
+
+        // in UTC the release is on the 4th
+        let ics: ICalendar = ICS.parse().unwrap();
+        assert_eq!(ics.events()[1].start, n_date!(2024, 6, 4));
+
+        // an event without DTSTART is skipped, a broken structure is an error
+        let ics: ICalendar = "BEGIN:VEVENT\nSUMMARY:x\nEND:VEVENT".parse().unwrap();
+        assert!(ics.events().is_empty());
+        assert_eq!(ics.skipped()[0].summary.as_deref(), Some("x"));
+        assert!("BEGIN:VEVENT\nSUMMARY:x\n".parse::<ICalendar>().is_err());
+        assert!("BEGIN:VEVENT\nEND:VALARM".parse::<ICalendar>().is_err());
+    }
+
+    #[test]
+    fn test_zones() {
+        // Tuesdays at 23:00 UTC are Wednesdays in Tokyo
+        let ics = ICalendar::parse(
+            "BEGIN:VEVENT\n\
+             UID:sync@example.com\n\
+             DTSTART:20240604T230000Z\n\
+             DURATION:PT1H\n\
+             RRULE:FREQ=WEEKLY;BYDAY=TU;UNTIL=20240618T230000Z\n\
+             EXDATE:20240611T230000Z\n\
+             END:VEVENT",
+            FixedOffset::east_opt(9 * 3600).unwrap(),
+        )
+        .unwrap();
+        let event = &ics.events()[0];
+        assert_eq!(
+            event.dates(n_date!(2024, 6, 1)..=n_date!(2024, 6, 30)),
+            [n_date!(2024, 6, 5), n_date!(2024, 6, 19)]
+        );
+        assert_eq!(
+            event.times_between(n_date!(2024, 6, 19)..=n_date!(2024, 6, 19)),
+            [(
+                n_date!(2024, 6, 19).and_hms_opt(8, 0, 0).unwrap(),
+                n_date!(2024, 6, 19).and_hms_opt(9, 0, 0).unwrap()
+            )]
+        );
+
+        let mut ics = ics;
+        ics.set_timestamp(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap());
+        let output = ics.to_string();
+        assert!(output.contains("DTSTART:20240604T230000Z\r\n"));
+        assert!(output.contains("UNTIL=20240618T235959Z\r\n"));
+        assert!(output.contains("EXDATE:20240611T230000Z\r\n"));
+        let parsed = ICalendar::parse(&output, FixedOffset::east_opt(9 * 3600).unwrap()).unwrap();
+        assert_eq!(parsed.events(), ics.events());
+
+        // 10:00 in Tokyo is 01:00 UTC on the same Monday
+        let tokyo = ICalendar::parse(ICS, FixedOffset::east_opt(0).unwrap()).unwrap();
+        assert_eq!(
+            tokyo.events()[2].dates(n_date!(2024, 6, 1)..=n_date!(2024, 6, 30)),
+            [
+                n_date!(2024, 6, 3),
+                n_date!(2024, 6, 10),
+                n_date!(2024, 6, 24)
+            ]
+        );
+        let output = tokyo.to_string();
+        assert!(output.contains("TZID:UTC+0900\r\n"));
+        assert!(output.contains("DTSTART;TZID=UTC+0900:20240603T100000\r\n"));
+        let parsed: ICalendar = output.parse().unwrap();
+        assert_eq!(parsed.events()[2].times, tokyo.events()[2].times);
+        assert_eq!(parsed.events()[2].zone, tokyo.events()[2].zone);
+
+        // an unknown TZID only leaves out its event
+        let named = ICS.replace("UTC+0900", "Asia/Tokyo");
+        let parsed: ICalendar = named.parse().unwrap();
+        #[cfg(not(feature = "tz"))]
+        {
+            assert_eq!(parsed.events().len(), 2);
+            assert_eq!(parsed.skipped()[0].summary.as_deref(), Some("Weekly sync"));
+            assert!(parsed.skipped()[0]
+                .error
+                .to_string()
+                .contains("unknown TZID `Asia/Tokyo`"));
+        }
+        #[cfg(feature = "tz")]
+        {
+            assert_eq!(
+                parsed.events()[2].dates(n_date!(2024, 6, 1)..=n_date!(2024, 6, 30)),
+                tokyo.events()[2].dates(n_date!(2024, 6, 1)..=n_date!(2024, 6, 30))
+            );
+            let output = parsed.to_string();
+            assert!(output.contains("BEGIN:VTIMEZONE\r\nTZID:Asia/Tokyo\r\n"));
+            assert!(output.contains("TZOFFSETTO:+0900\r\n"));
+        }
+    }
+
+    #[test]
+    fn test_vtimezone() {
+        // as exported by Outlook: a Windows zone name defined by a VTIMEZONE
+        let ics = "BEGIN:VCALENDAR\r
+BEGIN:VTIMEZONE\r
+TZID:Eastern Standard Time\r
+BEGIN:STANDARD\r
+DTSTART:16010101T020000\r
+TZOFFSETFROM:-0400\r
+TZOFFSETTO:-0500\r
+RRULE:FREQ=YEARLY;BYDAY=1SU;BYMONTH=11\r
+END:STANDARD\r
+BEGIN:DAYLIGHT\r
+DTSTART:16010101T020000\r
+TZOFFSETFROM:-0500\r
+TZOFFSETTO:-0400\r
+RRULE:FREQ=YEARLY;BYDAY=2SU;BYMONTH=3\r
+END:DAYLIGHT\r
+END:VTIMEZONE\r
+BEGIN:VEVENT\r
+UID:standup\r
+DTSTART;TZID=Eastern Standard Time:20240307T200000\r
+DTEND;TZID=Eastern Standard Time:20240307T203000\r
+RRULE:FREQ=WEEKLY;COUNT=2\r
+SUMMARY:Standup\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:review\r
+DTSTART;TZID=Eastern Standard Time:20240329T150000\r
+RRULE:FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1\r
+SUMMARY:Review\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+        let ics = ICalendar::parse(ics, FixedOffset::east_opt(0).unwrap()).unwrap();
+
+        // 20:00 is 01:00 UTC in standard time and 00:00 UTC after the change
+        let standup = &ics.events()[0];
+        assert_eq!(
+            standup.times_between(n_date!(2024, 3, 1)..=n_date!(2024, 3, 31)),
+            [
+                (
+                    n_date!(2024, 3, 8).and_hms_opt(1, 0, 0).unwrap(),
+                    n_date!(2024, 3, 8).and_hms_opt(1, 30, 0).unwrap()
+                ),
+                (
+                    n_date!(2024, 3, 15).and_hms_opt(0, 0, 0).unwrap(),
+                    n_date!(2024, 3, 15).and_hms_opt(0, 30, 0).unwrap()
+                )
+            ]
+        );
+
+        assert_eq!(ics.skipped().len(), 1);
+        assert_eq!(ics.skipped()[0].uid.as_deref(), Some("review"));
+        assert!(ics.skipped()[0].error.to_string().contains("BYSETPOS"));
+
+        // the zone is written back with its rules (with `tz`, it is America/New_York)
+        let output = ics.to_string();
+        let tzid = if cfg!(feature = "tz") {
+            "America/New_York"
+        } else {
+            "Eastern Standard Time"
+        };
+        assert!(output.contains(&format!("BEGIN:VTIMEZONE\r\nTZID:{}\r\n", tzid)));
+        assert!(output.contains("RRULE:FREQ=YEARLY;BYDAY=2SU;BYMONTH=3\r\n"));
+        assert!(output.contains("DTSTART:19700308T020000\r\n"));
+        let parsed = ICalendar::parse(&output, FixedOffset::east_opt(0).unwrap()).unwrap();
+        assert_eq!(parsed.events(), ics.events());
+    }
+
+    #[test]
+    fn test_export() {
+        let mut cal = YearCalendar::new(2024, Weekday::Sun, 3, BasicMarker::SquareBrackets);
+        cal.mark_range(n_date!(2024, 1, 30)..=n_date!(2024, 2, 2));
+        cal.annotate(n_date!(2024, 1, 31), "Sprint review; demo");
+        cal.mark_category(
+            n_date!(2024, 3, 8),
+            &Category::new("on-call", BasicMarker::Char('!')),
+        );
+
+        let mut ics = ICalendar::from_calendar(&cal, EventSpan::Contiguous, "example.com");
+        ics.set_timestamp(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap());
+        let output = ics.to_string();
+
+        assert!(output.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(output.contains("DTSTART;VALUE=DATE:20240130\r\nDTEND;VALUE=DATE:20240203\r\n"));
+        assert!(output.contains("DESCRIPTION:Sprint review\\; demo\r\n"));
+        assert!(output.contains("CATEGORIES:on-call\r\n"));
+        assert!(output.lines().all(|line| line.len() <= 75));
+
+        let parsed: ICalendar = output.parse().unwrap();
+        assert_eq!(parsed.events(), ics.events());
+        assert_eq!(ics.to_string(), output);
+        // the DTSTAMP of the parse is not compared
+        assert_eq!(output.parse::<ICalendar>().unwrap(), parsed);
+
+        // UIDs only depend on the event itself
+        let uids = |ics: &ICalendar| -> Vec<Option<String>> {
+            ics.events().iter().map(|e| e.uid.clone()).collect()
+        };
+        assert_eq!(
+            ics.events()[0].uid.as_deref(),
+            Some("20240130-20240202-marked-eddcc52b15488641@example.com")
+        );
+        cal.mark(n_date!(2024, 1, 10));
+        let marked = ICalendar::from_calendar(&cal, EventSpan::Contiguous, "example.com");
+        assert_eq!(uids(&marked)[1..], uids(&ics));
+
+        assert_eq!(
+            ICalendar::from_calendar(&cal, EventSpan::Day, "example.com")
+                .events()
+                .len(),
+            6
+        );
+    }
+}
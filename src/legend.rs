@@ -0,0 +1,255 @@
+use std::fmt::Display;
+
+use chrono::NaiveDate;
+
+use crate::{
+    forward::forward_calendar,
+    utils::{center, display_width},
+    Calendar, Canvas, Category, Holiday, Region,
+};
+
+/// List of markers with their labels
+///
+/// Each line is a decorated sample followed by the label:
+/// ```text
+/// [  ] marked
+/// *  * holiday
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Legend {
+    /// (sample, label)
+    entries: Vec<(String, String)>,
+}
+
+impl Legend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Legend of the categories and holidays used in `calendar`
+    pub fn from_calendar(calendar: &dyn Calendar) -> Self {
+        let mut legend = Self::new();
+
+        for category in calendar.categories() {
+            legend.add_category(&category);
+        }
+        legend.add_holidays(&calendar.holidays());
+
+        legend
+    }
+
+    pub fn add(&mut self, sample: impl Into<String>, label: impl Into<String>) {
+        self.entries.push((sample.into(), label.into()));
+    }
+
+    pub fn add_category(&mut self, category: &Category) {
+        self.add(category.marker().decorate("  "), category.name());
+    }
+
+    /// Add `Jan  1 New Year's Day` style lines
+    pub fn add_holidays(&mut self, holidays: &[Holiday]) {
+        for holiday in holidays {
+            self.add(holiday.date.format("%b %e").to_string(), &holiday.name);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn sample_width(&self) -> usize {
+        self.entries
+            .iter()
+            .map(|(sample, _)| display_width(sample))
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+impl Calendar for Legend {
+    fn mark(&mut self, _date: NaiveDate) {}
+    fn unmark(&mut self, _date: NaiveDate) {}
+
+    fn is_marked(&self, _date: NaiveDate) -> bool {
+        false
+    }
+
+    fn width(&self) -> usize {
+        let sample_width = self.sample_width();
+
+        self.entries
+            .iter()
+            .map(|(_, label)| sample_width + 1 + display_width(label))
+            .max()
+            .unwrap_or_default()
+    }
+
+    fn height(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn draw(&self, region: &mut Region<'_>) {
+        let sample_width = self.sample_width();
+
+        for (row, (sample, label)) in self.entries.iter().enumerate() {
+            region.write(row, &format!("{} {}", center(sample, sample_width), label));
+        }
+    }
+}
+
+impl Display for Legend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.render(f)
+    }
+}
+
+/// Where the legend is placed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LegendPosition {
+    /// Under the calendar, after a blank line
+    Below,
+    /// On the right of the calendar, after two spaces
+    Right,
+}
+
+const RIGHT_GAP: usize = 2;
+
+/// Calendar with an automatically generated [`Legend`]
+///
+/// The legend follows the categories and holidays marked in the calendar, including marks
+/// made through a [`MarkStore`](crate::MarkStore): it is built for each size query and once
+/// for each `draw` or `render`, not kept.
+#[derive(Debug)]
+pub struct WithLegend<C: Calendar> {
+    calendar: C,
+    position: LegendPosition,
+}
+
+impl<C: Calendar> WithLegend<C> {
+    pub fn new(calendar: C, position: LegendPosition) -> Self {
+        Self { calendar, position }
+    }
+
+    pub fn inner(&self) -> &C {
+        &self.calendar
+    }
+
+    pub fn inner_mut(&mut self) -> &mut C {
+        &mut self.calendar
+    }
+
+    pub fn into_inner(self) -> C {
+        self.calendar
+    }
+
+    pub fn legend(&self) -> Legend {
+        Legend::from_calendar(&self.calendar)
+    }
+}
+
+impl<C: Calendar> WithLegend<C> {
+    /// Width and height with `legend`
+    fn size(&self, legend: &Legend) -> (usize, usize) {
+        let (width, height) = (self.calendar.width(), self.calendar.height());
+        if legend.is_empty() {
+            return (width, height);
+        }
+
+        match self.position {
+            LegendPosition::Below => (width.max(legend.width()), height + 1 + legend.height()),
+            LegendPosition::Right => (
+                width + RIGHT_GAP + legend.width(),
+                height.max(legend.height()),
+            ),
+        }
+    }
+
+    fn draw_with(&self, legend: &Legend, width: usize, region: &mut Region<'_>) {
+        self.calendar
+            .draw(&mut region.region(0, 0).with_width(self.calendar.width()));
+        if legend.is_empty() {
+            return;
+        }
+
+        match self.position {
+            LegendPosition::Below => {
+                let height = self.calendar.height();
+                region.write(height, &" ".repeat(width));
+                legend.draw(&mut region.region(0, height + 1));
+            }
+            LegendPosition::Right => {
+                legend.draw(
+                    &mut region
+                        .region(self.calendar.width() + RIGHT_GAP, 0)
+                        .with_width(legend.width()),
+                );
+            }
+        }
+    }
+}
+
+impl<C: Calendar> Calendar for WithLegend<C> {
+    fn width(&self) -> usize {
+        self.size(&self.legend()).0
+    }
+
+    fn height(&self) -> usize {
+        self.size(&self.legend()).1
+    }
+
+    fn draw(&self, region: &mut Region<'_>) {
+        let legend = self.legend();
+        let (width, _) = self.size(&legend);
+
+        self.draw_with(&legend, width, region);
+    }
+
+    fn render(&self, w: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        let legend = self.legend();
+        let (width, height) = self.size(&legend);
+        let mut canvas = Canvas::new();
+        self.draw_with(&legend, width, &mut canvas.region());
+
+        canvas.write_to(w, width, height)
+    }
+
+    forward_calendar!(calendar);
+}
+
+impl<C: Calendar> Display for WithLegend<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.render(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{n_date, BasicMarker, MonthCalendar};
+
+    use super::*;
+
+    #[test]
+    fn test() {
+        let mut calendar = MonthCalendar::from_ym(2024, 6).unwrap();
+        calendar.mark(n_date!(2024, 6, 3));
+        calendar.mark_category(
+            n_date!(2024, 6, 4),
+            &Category::new("on-call", BasicMarker::Char('!')),
+        );
+
+        for position in [LegendPosition::Below, LegendPosition::Right] {
+            let with_legend = WithLegend::new(calendar, position);
+            let output = with_legend.to_string();
+            let lines: Vec<&str> = output.split('\n').collect();
+
+            assert_eq!(lines.len(), with_legend.height());
+            assert!(lines
+                .iter()
+                .all(|line| display_width(line) == with_legend.width()));
+            assert!(output.contains("[  ] marked"));
+            assert!(output.contains("!  ! on-call"));
+
+            calendar = with_legend.into_inner();
+        }
+    }
+}
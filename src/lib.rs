@@ -1,16 +1,63 @@
+mod agenda;
+mod alignment;
 mod calendar;
 mod calendar_collection;
 mod calendars;
+mod canvas;
+mod category;
+mod day_label;
+mod day_view;
 mod empty_calendar;
+mod error;
+mod footnote;
+mod forward;
+mod header;
+mod heatmap;
+mod holiday;
+mod ical;
+mod legend;
+mod lunar;
+mod mark_store;
 mod marker;
 mod month_calendar;
+mod month_calendar_builder;
+mod range_marker;
+mod recurrence;
+mod stack;
+mod terminal;
+mod today;
 pub(crate) mod utils;
+mod workweek;
 mod year_calender;
+mod zone;
 
+pub use agenda::*;
+pub use alignment::*;
 pub use calendar::*;
 pub use calendar_collection::*;
 pub use calendars::*;
+pub use canvas::*;
+pub use category::*;
+pub use day_label::*;
+pub use day_view::*;
 pub use empty_calendar::*;
+pub use error::*;
+pub use footnote::*;
+pub use header::*;
+pub use heatmap::*;
+pub use holiday::*;
+pub use ical::*;
+pub use legend::*;
+pub use lunar::*;
+pub use mark_store::*;
 pub use marker::*;
 pub use month_calendar::*;
+pub use month_calendar_builder::*;
+pub use range_marker::*;
+pub use recurrence::*;
+pub use stack::*;
+pub use terminal::*;
+pub use today::*;
+pub use workweek::*;
 pub use year_calender::*;
+pub use zone::*;
@@ -0,0 +1,499 @@
+use std::f64::consts::PI;
+use std::fmt::Display;
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::DayLabel;
+
+const MONTH_NAMES: [&str; 12] = [
+    "正月", "二月", "三月", "四月", "五月", "六月", "七月", "八月", "九月", "十月", "冬月", "腊月",
+];
+
+const DAY_NAMES: [&str; 30] = [
+    "初一", "初二", "初三", "初四", "初五", "初六", "初七", "初八", "初九", "初十", "十一", "十二",
+    "十三", "十四", "十五", "十六", "十七", "十八", "十九", "二十", "廿一", "廿二", "廿三", "廿四",
+    "廿五", "廿六", "廿七", "廿八", "廿九", "三十",
+];
+
+/// 24 solar terms (节气), starting from the March equinox (solar longitude 0°)
+const SOLAR_TERM_NAMES: [&str; 24] = [
+    "春分", "清明", "谷雨", "立夏", "小满", "芒种", "夏至", "小暑", "大暑", "立秋", "处暑", "白露",
+    "秋分", "寒露", "霜降", "立冬", "小雪", "大雪", "冬至", "小寒", "大寒", "立春", "雨水", "惊蛰",
+];
+
+/// Date in a Chinese-style lunisolar calendar
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LunarDate {
+    /// Gregorian year in which the lunar year begins
+    pub year: i32,
+    /// 1..=12
+    pub month: u32,
+    /// 1..=30
+    pub day: u32,
+    /// Whether the month is a leap (intercalary) month
+    pub leap: bool,
+}
+
+impl LunarDate {
+    /// e.g. `正月`, `闰四月`
+    pub fn month_name(&self) -> String {
+        let name = MONTH_NAMES[self.month as usize - 1];
+        if self.leap {
+            format!("闰{}", name)
+        } else {
+            name.to_string()
+        }
+    }
+
+    /// e.g. `初一`, `廿九`
+    pub fn day_name(&self) -> &'static str {
+        DAY_NAMES[self.day as usize - 1]
+    }
+}
+
+impl Display for LunarDate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.month_name(), self.day_name())
+    }
+}
+
+/// One of the 24 solar terms (节气)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SolarTerm(u8);
+
+impl SolarTerm {
+    /// Solar longitude of the term in degrees (multiple of 15)
+    pub fn longitude(&self) -> u32 {
+        self.0 as u32 * 15
+    }
+
+    /// e.g. `立春`
+    pub fn name(&self) -> &'static str {
+        SOLAR_TERM_NAMES[self.0 as usize]
+    }
+
+    /// Principal terms (中气) decide the numbering of lunar months
+    // `is_multiple_of` would need Rust 1.87
+    #[allow(clippy::manual_is_multiple_of)]
+    pub fn is_principal(&self) -> bool {
+        self.0 % 2 == 0
+    }
+}
+
+impl Display for SolarTerm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Lunisolar calendar computed from mean astronomical positions
+///
+/// Dates are decided in the local time of the calendar
+/// (UTC+8 for the Chinese calendar, UTC+9 for the Korean one).
+///
+/// The positions of the sun and the moon are low-precision ones, off by up to about 15
+/// minutes, so a new moon or a solar term that close to midnight can fall on the wrong day.
+/// Before 1929 China used Beijing local time (UTC+7:46), which this does not follow.
+/// Results match published calendars for the years in the tests, not for every year.
+#[derive(Debug, Clone, Copy)]
+pub struct Lunisolar {
+    utc_offset_hours: f64,
+}
+
+impl Default for Lunisolar {
+    fn default() -> Self {
+        Self::chinese()
+    }
+}
+
+impl Lunisolar {
+    /// Chinese calendar (农历), Beijing time
+    pub fn chinese() -> Self {
+        Self {
+            utc_offset_hours: 8.0,
+        }
+    }
+
+    /// Korean calendar (음력), Seoul time
+    pub fn korean() -> Self {
+        Self {
+            utc_offset_hours: 9.0,
+        }
+    }
+
+    /// Convert a Gregorian date into the lunar date
+    pub fn lunar_date(&self, date: NaiveDate) -> LunarDate {
+        let next_solstice = self.winter_solstice(date.year());
+        let sui_end_year = if date >= self.month_start(next_solstice) {
+            date.year() + 1
+        } else {
+            date.year()
+        };
+
+        let months = self.sui(sui_end_year);
+        let (start, month, leap) = months
+            .iter()
+            .rev()
+            .find(|(start, _, _)| *start <= date)
+            .copied()
+            .expect("the date is in the sui");
+
+        // months 11 and 12 belong to the previous lunar year
+        let year = if month >= 11 {
+            sui_end_year - 1
+        } else {
+            sui_end_year
+        };
+
+        LunarDate {
+            year,
+            month,
+            day: (date - start).num_days() as u32 + 1,
+            leap,
+        }
+    }
+
+    /// The solar term that begins on the date, if any
+    pub fn solar_term(&self, date: NaiveDate) -> Option<SolarTerm> {
+        let start = jd_from_date(date) - self.utc_offset_hours / 24.0;
+        let begin = solar_longitude(tt_from_ut(start)) / 15.0;
+        let end = solar_longitude(tt_from_ut(start + 1.0)) / 15.0;
+
+        if begin.floor() == end.floor() {
+            return None;
+        }
+
+        Some(SolarTerm((end.floor() as u8) % 24))
+    }
+
+    /// Months of the sui (岁) between the winter solstices of `year - 1` and `year`
+    ///
+    /// Returns `(first day, month number, leap)` for each month, starting with the 11th month.
+    fn sui(&self, year: i32) -> Vec<(NaiveDate, u32, bool)> {
+        let first_solstice = self.winter_solstice(year - 1);
+        let k_begin = self.lunation_before(first_solstice);
+        let k_end = self.lunation_before(self.winter_solstice(year));
+
+        let starts: Vec<NaiveDate> = (k_begin..=k_end)
+            .map(|k| self.local_date(new_moon(k as f64)))
+            .collect();
+
+        // In a sui with 13 months, the first one without a principal term is the leap month
+        let mut leap_index = None;
+        if k_end - k_begin == 13 {
+            let mut jde = solar_longitude_time(270.0, first_solstice);
+            let mut principal_terms = vec![];
+            for i in 1..=12 {
+                jde = solar_longitude_time(((270 + 30 * i) % 360) as f64, jde + 30.0);
+                principal_terms.push(self.local_date(jde));
+            }
+
+            leap_index = (1..13).find(|&i| {
+                !principal_terms
+                    .iter()
+                    .any(|d| starts[i] <= *d && *d < starts[i + 1])
+            });
+        }
+
+        let mut month = 10;
+        let mut result = vec![];
+        for (i, start) in starts[..starts.len() - 1].iter().enumerate() {
+            let leap = leap_index == Some(i);
+            if !leap {
+                month = month % 12 + 1;
+            }
+            result.push((*start, month, leap));
+        }
+
+        result
+    }
+
+    /// JDE of the December solstice of the year
+    fn winter_solstice(&self, year: i32) -> f64 {
+        let guess = jd_from_date(NaiveDate::from_ymd_opt(year, 12, 21).unwrap());
+        solar_longitude_time(270.0, guess)
+    }
+
+    /// Lunation number of the last new moon on or before the local date of `jde`
+    fn lunation_before(&self, jde: f64) -> i64 {
+        let date = self.local_date(jde);
+        let mut k = ((jde - 2451550.09766) / 29.530588861).floor() as i64;
+
+        while self.local_date(new_moon((k + 1) as f64)) <= date {
+            k += 1;
+        }
+        while self.local_date(new_moon(k as f64)) > date {
+            k -= 1;
+        }
+
+        k
+    }
+
+    /// First day of the lunar month containing `jde`
+    fn month_start(&self, jde: f64) -> NaiveDate {
+        self.local_date(new_moon(self.lunation_before(jde) as f64))
+    }
+
+    fn local_date(&self, jde: f64) -> NaiveDate {
+        let jd = ut_from_tt(jde) + self.utc_offset_hours / 24.0;
+        let days = (jd - UNIX_EPOCH_JD).floor() as i64;
+
+        NaiveDate::from_ymd_opt(1970, 1, 1).unwrap() + chrono::Duration::days(days)
+    }
+}
+
+/// Shows the lunar day under each day number
+///
+/// The first day of a lunar month shows the month name and
+/// the first day of a solar term shows the term instead.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LunarLabel {
+    calendar: Lunisolar,
+}
+
+impl LunarLabel {
+    pub fn new(calendar: Lunisolar) -> Self {
+        Self { calendar }
+    }
+}
+
+impl DayLabel for LunarLabel {
+    fn label(&self, date: NaiveDate) -> Option<String> {
+        if let Some(term) = self.calendar.solar_term(date) {
+            return Some(term.name().to_string());
+        }
+
+        let lunar = self.calendar.lunar_date(date);
+        if lunar.day == 1 {
+            Some(lunar.month_name())
+        } else {
+            Some(lunar.day_name().to_string())
+        }
+    }
+}
+
+const UNIX_EPOCH_JD: f64 = 2440587.5;
+
+fn jd_from_date(date: NaiveDate) -> f64 {
+    let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+    UNIX_EPOCH_JD + (date - epoch).num_days() as f64
+}
+
+/// ΔT = TT - UT in seconds (Espenak & Meeus polynomial expressions)
+fn delta_t(jd: f64) -> f64 {
+    let y = 2000.0 + (jd - 2451544.5) / 365.2425;
+
+    if y < 1900.0 {
+        let t = y - 1860.0;
+        7.62 + 0.5737 * t - 0.251754 * t.powi(2) + 0.01680668 * t.powi(3) - 0.0004473624 * t.powi(4)
+            + t.powi(5) / 233174.0
+    } else if y < 1920.0 {
+        let t = y - 1900.0;
+        -2.79 + 1.494119 * t - 0.0598939 * t.powi(2) + 0.0061966 * t.powi(3) - 0.000197 * t.powi(4)
+    } else if y < 1941.0 {
+        let t = y - 1920.0;
+        21.20 + 0.84493 * t - 0.076100 * t.powi(2) + 0.0020936 * t.powi(3)
+    } else if y < 1961.0 {
+        let t = y - 1950.0;
+        29.07 + 0.407 * t - t.powi(2) / 233.0 + t.powi(3) / 2547.0
+    } else if y < 1986.0 {
+        let t = y - 1975.0;
+        45.45 + 1.067 * t - t.powi(2) / 260.0 - t.powi(3) / 718.0
+    } else if y < 2005.0 {
+        let t = y - 2000.0;
+        63.86 + 0.3345 * t - 0.060374 * t.powi(2)
+            + 0.0017275 * t.powi(3)
+            + 0.000651814 * t.powi(4)
+            + 0.00002373599 * t.powi(5)
+    } else if y < 2050.0 {
+        let t = y - 2000.0;
+        62.92 + 0.32217 * t + 0.005589 * t.powi(2)
+    } else {
+        -20.0 + 32.0 * ((y - 1820.0) / 100.0).powi(2) - 0.5628 * (2150.0 - y)
+    }
+}
+
+fn tt_from_ut(jd: f64) -> f64 {
+    jd + delta_t(jd) / 86400.0
+}
+
+fn ut_from_tt(jde: f64) -> f64 {
+    jde - delta_t(jde) / 86400.0
+}
+
+fn sin_deg(deg: f64) -> f64 {
+    (deg * PI / 180.0).sin()
+}
+
+/// Apparent solar longitude in degrees (Meeus, Astronomical Algorithms, ch. 25, to about
+/// 0.01°)
+fn solar_longitude(jde: f64) -> f64 {
+    let t = (jde - 2451545.0) / 36525.0;
+
+    let l0 = 280.46646 + 36000.76983 * t + 0.0003032 * t * t;
+    let m = 357.52911 + 35999.05029 * t - 0.0001537 * t * t;
+    let c = (1.914602 - 0.004817 * t - 0.000014 * t * t) * sin_deg(m)
+        + (0.019993 - 0.000101 * t) * sin_deg(2.0 * m)
+        + 0.000289 * sin_deg(3.0 * m);
+    let omega = 125.04 - 1934.136 * t;
+
+    (l0 + c - 0.00569 - 0.00478 * sin_deg(omega)).rem_euclid(360.0)
+}
+
+/// JDE at which the sun reaches `longitude`, searching near `guess`
+fn solar_longitude_time(longitude: f64, guess: f64) -> f64 {
+    let mut jde = guess;
+
+    for _ in 0..50 {
+        let correction = 58.0 * sin_deg(longitude - solar_longitude(jde));
+        jde += correction;
+
+        if correction.abs() < 1e-7 {
+            break;
+        }
+    }
+
+    jde
+}
+
+/// JDE of the new moon with lunation number `k` (k = 0 at 2000-01-06; Meeus, ch. 49)
+fn new_moon(k: f64) -> f64 {
+    let t = k / 1236.85;
+
+    let mut jde = 2451550.09766 + 29.530588861 * k + 0.00015437 * t.powi(2)
+        - 0.000000150 * t.powi(3)
+        + 0.00000000073 * t.powi(4);
+
+    let e = 1.0 - 0.002516 * t - 0.0000074 * t * t;
+    let m = 2.5534 + 29.10535670 * k - 0.0000014 * t.powi(2) - 0.00000011 * t.powi(3);
+    let mp = 201.5643 + 385.81693528 * k + 0.0107582 * t.powi(2) + 0.00001238 * t.powi(3)
+        - 0.000000058 * t.powi(4);
+    let f = 160.7108 + 390.67050284 * k - 0.0016118 * t.powi(2) - 0.00000227 * t.powi(3)
+        + 0.000000011 * t.powi(4);
+    let omega = 124.7746 - 1.56375588 * k + 0.0020672 * t.powi(2) + 0.00000215 * t.powi(3);
+
+    jde += -0.40720 * sin_deg(mp)
+        + 0.17241 * e * sin_deg(m)
+        + 0.01608 * sin_deg(2.0 * mp)
+        + 0.01039 * sin_deg(2.0 * f)
+        + 0.00739 * e * sin_deg(mp - m)
+        - 0.00514 * e * sin_deg(mp + m)
+        + 0.00208 * e * e * sin_deg(2.0 * m)
+        - 0.00111 * sin_deg(mp - 2.0 * f)
+        - 0.00057 * sin_deg(mp + 2.0 * f)
+        + 0.00056 * e * sin_deg(2.0 * mp + m)
+        - 0.00042 * sin_deg(3.0 * mp)
+        + 0.00042 * e * sin_deg(m + 2.0 * f)
+        + 0.00038 * e * sin_deg(m - 2.0 * f)
+        - 0.00024 * e * sin_deg(2.0 * mp - m)
+        - 0.00017 * sin_deg(omega)
+        - 0.00007 * sin_deg(mp + 2.0 * m)
+        + 0.00004 * sin_deg(2.0 * mp - 2.0 * f)
+        + 0.00004 * sin_deg(3.0 * m)
+        + 0.00003 * sin_deg(mp + m - 2.0 * f)
+        + 0.00003 * sin_deg(2.0 * mp + 2.0 * f)
+        - 0.00003 * sin_deg(mp + m + 2.0 * f)
+        + 0.00003 * sin_deg(mp - m + 2.0 * f)
+        - 0.00002 * sin_deg(mp - m - 2.0 * f)
+        - 0.00002 * sin_deg(3.0 * mp + m)
+        + 0.00002 * sin_deg(4.0 * mp);
+
+    // planetary arguments
+    let planetary: [(f64, f64, f64); 14] = [
+        (299.77, 0.107408, 0.000325),
+        (251.88, 0.016321, 0.000165),
+        (251.83, 26.651886, 0.000164),
+        (349.42, 36.412478, 0.000126),
+        (84.66, 18.206239, 0.000110),
+        (141.74, 53.303771, 0.000062),
+        (207.14, 2.453732, 0.000060),
+        (154.84, 7.306860, 0.000056),
+        (34.52, 27.261239, 0.000047),
+        (207.19, 0.121824, 0.000042),
+        (291.34, 1.844379, 0.000040),
+        (161.72, 24.198154, 0.000037),
+        (239.56, 25.513099, 0.000035),
+        (331.55, 3.592518, 0.000023),
+    ];
+
+    for (i, (base, rate, coefficient)) in planetary.iter().enumerate() {
+        let mut a = base + rate * k;
+        if i == 0 {
+            a -= 0.009173 * t * t;
+        }
+        jde += coefficient * sin_deg(a);
+    }
+
+    jde
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::n_date;
+
+    use super::*;
+
+    #[test]
+    fn test_new_year() {
+        let new_years = [
+            n_date!(1900, 1, 31),
+            n_date!(1912, 2, 18),
+            n_date!(1949, 1, 29),
+            n_date!(1970, 2, 6),
+            n_date!(1985, 2, 20),
+            n_date!(2000, 2, 5),
+            n_date!(2017, 1, 28),
+            n_date!(2023, 1, 22),
+            n_date!(2024, 2, 10),
+            n_date!(2025, 1, 29),
+            n_date!(2033, 1, 31),
+            n_date!(2050, 1, 23),
+            n_date!(2100, 2, 9),
+        ];
+
+        let calendar = Lunisolar::chinese();
+        for date in new_years {
+            let lunar = calendar.lunar_date(date);
+            assert_eq!(
+                lunar,
+                LunarDate {
+                    year: date.year(),
+                    month: 1,
+                    day: 1,
+                    leap: false
+                },
+                "{}",
+                date
+            );
+            assert_eq!(calendar.lunar_date(date.pred_opt().unwrap()).month, 12);
+        }
+    }
+
+    #[test]
+    fn test_leap_month() {
+        let calendar = Lunisolar::chinese();
+
+        // 闰四月 2020-05-23
+        let lunar = calendar.lunar_date(n_date!(2020, 5, 23));
+        assert_eq!((lunar.month, lunar.day, lunar.leap), (4, 1, true));
+
+        // 闰十一月 2033-12-22
+        let lunar = calendar.lunar_date(n_date!(2033, 12, 22));
+        assert_eq!((lunar.month, lunar.day, lunar.leap), (11, 1, true));
+    }
+
+    #[test]
+    fn test_solar_term() {
+        let calendar = Lunisolar::chinese();
+
+        assert_eq!(
+            calendar.solar_term(n_date!(2024, 2, 4)).map(|t| t.name()),
+            Some("立春")
+        );
+        assert_eq!(
+            calendar.solar_term(n_date!(2024, 12, 21)).map(|t| t.name()),
+            Some("冬至")
+        );
+        assert_eq!(calendar.solar_term(n_date!(2024, 12, 22)), None);
+    }
+}
@@ -0,0 +1,147 @@
+use std::{
+    cell::RefCell,
+    collections::{BTreeSet, HashSet},
+    fmt::Debug,
+    ops::RangeInclusive,
+    rc::Rc,
+};
+
+use chrono::NaiveDate;
+
+/// Marked dates looked up by the calendars sharing them
+///
+/// A store set with [`Calendar::set_mark_store`](crate::Calendar::set_mark_store) is read
+/// at render time, so wrapping it in `Rc<RefCell<_>>` and updating it once is reflected
+/// in every calendar holding it:
+/// ```
+/// use std::{cell::RefCell, collections::BTreeSet, rc::Rc};
+///
+/// use chrono::{NaiveDate, Weekday};
+/// use text_calendar::{BasicMarker, Calendar, MonthCalendar, YearCalendar};
+///
+/// let store = Rc::new(RefCell::new(BTreeSet::new()));
+///
+/// let mut year = YearCalendar::new(2024, Weekday::Sun, 3, BasicMarker::SquareBrackets);
+/// let mut june = MonthCalendar::from_ym(2024, 6).unwrap();
+/// year.set_mark_store(Some(store.clone()));
+/// june.set_mark_store(Some(store.clone()));
+///
+/// let date = NaiveDate::from_ymd_opt(2024, 6, 3).unwrap();
+/// store.borrow_mut().insert(date);
+/// assert!(year.is_marked(date) && june.is_marked(date));
+/// ```
+pub trait MarkStore {
+    fn contains(&self, date: NaiveDate) -> bool;
+
+    /// Marked dates in the range, in order
+    fn marked_between(&self, range: RangeInclusive<NaiveDate>) -> Vec<NaiveDate> {
+        range
+            .start()
+            .iter_days()
+            .take_while(|d| d <= range.end())
+            .filter(|d| self.contains(*d))
+            .collect()
+    }
+}
+
+impl MarkStore for BTreeSet<NaiveDate> {
+    fn contains(&self, date: NaiveDate) -> bool {
+        BTreeSet::contains(self, &date)
+    }
+
+    fn marked_between(&self, range: RangeInclusive<NaiveDate>) -> Vec<NaiveDate> {
+        self.range(range).copied().collect()
+    }
+}
+
+impl MarkStore for HashSet<NaiveDate> {
+    fn contains(&self, date: NaiveDate) -> bool {
+        HashSet::contains(self, &date)
+    }
+}
+
+impl<S: MarkStore + ?Sized> MarkStore for RefCell<S> {
+    fn contains(&self, date: NaiveDate) -> bool {
+        self.borrow().contains(date)
+    }
+
+    fn marked_between(&self, range: RangeInclusive<NaiveDate>) -> Vec<NaiveDate> {
+        self.borrow().marked_between(range)
+    }
+}
+
+impl<S: MarkStore + ?Sized> MarkStore for Rc<S> {
+    fn contains(&self, date: NaiveDate) -> bool {
+        (**self).contains(date)
+    }
+
+    fn marked_between(&self, range: RangeInclusive<NaiveDate>) -> Vec<NaiveDate> {
+        (**self).marked_between(range)
+    }
+}
+
+impl<S: MarkStore + ?Sized> MarkStore for &S {
+    fn contains(&self, date: NaiveDate) -> bool {
+        (**self).contains(date)
+    }
+
+    fn marked_between(&self, range: RangeInclusive<NaiveDate>) -> Vec<NaiveDate> {
+        (**self).marked_between(range)
+    }
+}
+
+impl Debug for dyn MarkStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "MarkStore")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Weekday;
+
+    use crate::{n_date, Agenda, BasicMarker, Calendar, MonthCalendar, YearCalendar};
+
+    use super::*;
+
+    #[test]
+    fn test() {
+        let store = Rc::new(RefCell::new(BTreeSet::new()));
+        let mut year = YearCalendar::new(2024, Weekday::Sun, 4, BasicMarker::SquareBrackets);
+        let mut june = MonthCalendar::from_ym(2024, 6).unwrap();
+        year.set_mark_store(Some(store.clone()));
+        june.set_mark_store(Some(store.clone()));
+
+        let plain = june.to_string();
+        store.borrow_mut().insert(n_date!(2024, 6, 3));
+        store.borrow_mut().insert(n_date!(2024, 7, 1));
+
+        assert_ne!(june.to_string(), plain);
+        assert!(june.to_string().contains("[3 ]"));
+        assert!(!june.is_marked(n_date!(2024, 7, 1)));
+        assert!(year.is_marked(n_date!(2024, 7, 1)));
+        assert_eq!(year.categories_of(n_date!(2024, 6, 3)).len(), 1);
+
+        let mut agenda = Agenda::from_calendar(&year);
+        agenda.set_mark_store(Some(store.clone()));
+        assert!(agenda.to_string().contains("Mon  3 [  ] marked"));
+        assert!(agenda.to_string().contains("Mon  1 [  ] marked"));
+
+        // later changes of the store reach the agenda too
+        store.borrow_mut().remove(&n_date!(2024, 7, 1));
+        store.borrow_mut().insert(n_date!(2024, 6, 5));
+        let output = agenda.to_string();
+        assert!(output.contains("Wed  5 [  ] marked"));
+        assert!(!output.contains("July"));
+
+        // own marks are kept apart from the store
+        june.unmark(n_date!(2024, 6, 3));
+        assert!(june.is_marked(n_date!(2024, 6, 3)));
+
+        let hash: HashSet<NaiveDate> = [n_date!(2024, 6, 5)].into();
+        assert_eq!(
+            hash.marked_between(n_date!(2024, 6, 1)..=n_date!(2024, 6, 30)),
+            [n_date!(2024, 6, 5)]
+        );
+    }
+}
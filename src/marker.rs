@@ -1,4 +1,4 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, rc::Rc};
 
 /// Marker trait for calendar (width=4)
 pub trait Marker {
@@ -6,6 +6,13 @@ pub trait Marker {
     ///
     /// note: width is at least 2
     fn decorate(&self, day: &str) -> String;
+
+    /// Decorate content of a day with a value
+    ///
+    /// `values` are all the values of the calendar, sorted. Same as `decorate` by default.
+    fn decorate_value(&self, day: &str, _value: f64, _values: &[f64]) -> String {
+        self.decorate(day)
+    }
 }
 
 /// Basic marker
@@ -40,3 +47,79 @@ impl Debug for dyn Marker {
         write!(f, "{}", self.decorate(""))
     }
 }
+
+/// How a value is mapped to a level of [`ScaleMarker`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Scale {
+    /// Level `i` for values from the `i`-th threshold (level 0 below the first one)
+    Thresholds(Vec<f64>),
+
+    /// Levels of equal size by the rank of the value among the values of the calendar
+    Quantiles,
+}
+
+/// Value-aware marker picking one of several markers by the value of the day
+///
+/// ```
+/// use text_calendar::{Scale, ScaleMarker};
+///
+/// // `·` below 2, `*` below 5, `#` from 5
+/// let marker = ScaleMarker::chars(Scale::Thresholds(vec![2.0, 5.0]), "·*#");
+/// ```
+#[derive(Debug, Clone)]
+pub struct ScaleMarker {
+    scale: Scale,
+    markers: Vec<Rc<dyn Marker>>,
+}
+
+impl ScaleMarker {
+    /// Markers from the lowest level to the highest
+    ///
+    /// With `Scale::Thresholds`, there should be one more marker than thresholds.
+    pub fn new(scale: Scale, markers: Vec<Rc<dyn Marker>>) -> Self {
+        Self { scale, markers }
+    }
+
+    /// One [`BasicMarker::Char`] per level
+    pub fn chars(scale: Scale, chars: &str) -> Self {
+        Self::new(
+            scale,
+            chars
+                .chars()
+                .map(|c| Rc::new(BasicMarker::Char(c)) as Rc<dyn Marker>)
+                .collect(),
+        )
+    }
+
+    fn level(&self, value: f64, values: &[f64]) -> usize {
+        let last = self.markers.len().saturating_sub(1);
+
+        let level = match &self.scale {
+            Scale::Thresholds(thresholds) => thresholds.iter().filter(|t| value >= **t).count(),
+            Scale::Quantiles if values.is_empty() => last,
+            Scale::Quantiles => {
+                let below = values.partition_point(|v| *v < value);
+                below * self.markers.len() / values.len()
+            }
+        };
+
+        level.min(last)
+    }
+}
+
+impl Marker for ScaleMarker {
+    /// Decorate with the highest level
+    fn decorate(&self, day: &str) -> String {
+        match self.markers.last() {
+            Some(marker) => marker.decorate(day),
+            None => day.to_string(),
+        }
+    }
+
+    fn decorate_value(&self, day: &str, value: f64, values: &[f64]) -> String {
+        match self.markers.get(self.level(value, values)) {
+            Some(marker) => marker.decorate_value(day, value, values),
+            None => day.to_string(),
+        }
+    }
+}
@@ -1,9 +1,20 @@
-use std::{collections::HashSet, fmt::Display, ops::RangeInclusive};
-
-use chrono::{Datelike, Month, NaiveDate, Weekday};
-use num_traits::FromPrimitive;
-
-use crate::{BasicMarker, Calendar, Marker};
+use std::{
+    cell::{Ref, RefCell},
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+    ops::RangeInclusive,
+    rc::Rc,
+};
+
+use chrono::{Datelike, NaiveDate, Weekday};
+
+use crate::{
+    utils::{center, display_width, truncate, wrap},
+    BasicMarker, BracketRange, Calendar, Category, DayLabel, FootnoteStyle, Header, Holiday,
+    MarkStore, Marker, MarkerCombination, MonthCalendarBuilder, RangeMarker, RangePosition, Region,
+    TodayHighlight, WeekendStyle,
+};
 
 #[derive(Debug)]
 pub struct MonthCalendar {
@@ -15,8 +26,33 @@ pub struct MonthCalendar {
     begin_weekday: Weekday,
     day_width: usize,
 
-    marker: Box<dyn Marker>,
-    marked: HashSet<u32>,
+    /// known categories, `Category::MARKED` and `Category::HOLIDAY` first
+    categories: Vec<Category>,
+    /// day -> indices of `categories`
+    marks: BTreeMap<u32, BTreeSet<usize>>,
+    /// dates shown as marked besides `marks`
+    store: Option<Rc<dyn MarkStore>>,
+    combination: MarkerCombination,
+    /// day -> value of `set_value`
+    values: BTreeMap<u32, f64>,
+    /// all the values of `values`, sorted for value-aware markers
+    sorted_values: Vec<f64>,
+
+    ranges: Vec<RangeInclusive<NaiveDate>>,
+    range_marker: Rc<dyn RangeMarker>,
+
+    holidays: BTreeMap<u32, Vec<String>>,
+
+    annotations: BTreeMap<u32, Vec<String>>,
+    footnotes: Option<FootnoteStyle>,
+    /// wrapped footnotes, until the annotations or the style change
+    footnote_lines: RefCell<Option<Vec<String>>>,
+
+    day_label: Option<Rc<dyn DayLabel>>,
+    header: Header,
+
+    today: Option<TodayHighlight>,
+    weekend: Option<WeekendStyle>,
 }
 
 impl MonthCalendar {
@@ -26,6 +62,16 @@ impl MonthCalendar {
         begin_weekday: Weekday,
         day_width: usize,
         marker: impl Marker + 'static,
+    ) -> Option<Self> {
+        Self::with_marker(year, month, begin_weekday, day_width, Rc::new(marker))
+    }
+
+    pub(crate) fn with_marker(
+        year: i32,
+        month: u32,
+        begin_weekday: Weekday,
+        day_width: usize,
+        marker: Rc<dyn Marker>,
     ) -> Option<Self> {
         let date = NaiveDate::from_ymd_opt(year, month, 1)?;
 
@@ -57,105 +103,600 @@ impl MonthCalendar {
             weeks,
             begin_weekday,
             day_width,
-            marked: HashSet::new(),
-            marker: Box::new(marker),
+            categories: vec![
+                Category::from_rc(Category::MARKED, marker),
+                Category::holiday(),
+            ],
+            marks: BTreeMap::new(),
+            store: None,
+            combination: MarkerCombination::default(),
+            values: BTreeMap::new(),
+            sorted_values: vec![],
+            ranges: vec![],
+            range_marker: Rc::new(BracketRange::default()),
+            holidays: BTreeMap::new(),
+            annotations: BTreeMap::new(),
+            footnotes: None,
+            footnote_lines: RefCell::new(None),
+            day_label: None,
+            header: Header::default(),
+            today: None,
+            weekend: None,
         })
     }
 
     pub fn from_ym(year: i32, month: u32) -> Option<Self> {
         Self::new(year, month, Weekday::Sun, 4, BasicMarker::SquareBrackets)
     }
-}
 
-impl Calendar for MonthCalendar {
-    fn mark(&mut self, date: NaiveDate) {
-        let year = if let (true, year) = date.year_ce() {
-            year as i32
+    /// Builder with the defaults of `from_ym`
+    pub fn builder() -> MonthCalendarBuilder {
+        MonthCalendarBuilder::default()
+    }
+
+    /// Title, subtitle and weekday row above the grid
+    pub fn set_header(&mut self, header: Header) {
+        self.header = header;
+    }
+
+    pub(crate) fn set_day_label_rc(&mut self, label: Rc<dyn DayLabel>) {
+        self.day_label = Some(label);
+    }
+
+    /// Show a second row under each week with a label for every day (e.g. [`LunarLabel`](crate::LunarLabel))
+    pub fn set_day_label(&mut self, label: impl DayLabel + 'static) {
+        self.day_label = Some(Rc::new(label));
+    }
+
+    /// Show the annotations as footnotes under the grid, with references next to the days
+    pub fn set_footnotes(&mut self, style: FootnoteStyle) {
+        self.footnotes = Some(style);
+        self.footnote_lines.take();
+    }
+
+    fn date(&self, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(self.year, self.month, day).unwrap()
+    }
+
+    fn contains(&self, date: NaiveDate) -> bool {
+        date.year() == self.year && date.month() == self.month
+    }
+
+    /// Index of the category, registering it (or replacing the old definition) first
+    ///
+    /// `Category::MARKED` and `Category::HOLIDAY` keep their own definitions, which are
+    /// changed by the calendar's marker and `set_holiday_marker`.
+    fn register(&mut self, category: &Category) -> usize {
+        if let Some(i) = self.categories.iter().position(|c| c == category) {
+            if i >= 2 {
+                self.categories[i] = category.clone();
+            }
+            i
         } else {
-            -(date.year_ce().1 as i32)
+            self.categories.push(category.clone());
+            self.categories.len() - 1
+        }
+    }
+
+    fn insert_mark(&mut self, date: NaiveDate, index: usize) {
+        if self.contains(date) {
+            self.marks.entry(date.day()).or_default().insert(index);
+        }
+    }
+
+    fn remove_mark(&mut self, date: NaiveDate, index: usize) {
+        if !self.contains(date) {
+            return;
+        }
+
+        if let Some(indices) = self.marks.get_mut(&date.day()) {
+            indices.remove(&index);
+            if indices.is_empty() {
+                self.marks.remove(&date.day());
+            }
+        }
+    }
+
+    /// Whether the day is in the mark store
+    fn in_store(&self, day: u32) -> bool {
+        self.store
+            .as_ref()
+            .is_some_and(|store| store.contains(self.date(day)))
+    }
+
+    /// Categories of the day, sorted by precedence
+    fn day_categories(&self, day: u32) -> Vec<&Category> {
+        let mut categories: Vec<(usize, &Category)> = self
+            .marks
+            .get(&day)
+            .into_iter()
+            .flatten()
+            .map(|i| (*i, &self.categories[*i]))
+            .collect();
+        if categories.iter().all(|(i, _)| *i != 0) && self.in_store(day) {
+            categories.push((0, &self.categories[0]));
+        }
+        categories.sort_by_key(|(i, c)| (Reverse(c.priority()), *i));
+
+        categories.into_iter().map(|(_, c)| c).collect()
+    }
+
+    /// Footnote reference of the day
+    fn reference(&self, day: u32) -> Option<String> {
+        let style = self.footnotes?;
+        let n = self.annotations.keys().position(|d| *d == day)?;
+
+        Some(style.reference(n + 1))
+    }
+
+    /// Whether the date is in a range marked by `mark_range`
+    fn in_range(&self, date: Option<NaiveDate>) -> bool {
+        date.is_some_and(|date| self.ranges.iter().any(|range| range.contains(&date)))
+    }
+
+    /// Remove `cut` from the marked ranges, splitting the ones it is in the middle of
+    fn cut_ranges(&mut self, cut: RangeInclusive<NaiveDate>) {
+        let mut ranges = vec![];
+
+        for range in self.ranges.drain(..) {
+            if range.end() < cut.start() || cut.end() < range.start() {
+                ranges.push(range);
+                continue;
+            }
+
+            if let Some(end) = cut.start().pred_opt().filter(|end| range.start() <= end) {
+                ranges.push(*range.start()..=end);
+            }
+            if let Some(start) = cut.end().succ_opt().filter(|start| start <= range.end()) {
+                ranges.push(start..=*range.end());
+            }
+        }
+
+        self.ranges = ranges;
+    }
+
+    fn is_today(&self, day: u32) -> bool {
+        self.today
+            .as_ref()
+            .is_some_and(|today| today.today() == self.date(day))
+    }
+
+    /// Cell of `width` columns drawn by the range marker
+    ///
+    /// Days with a category besides [`Category::MARKED`] are left to their category marker.
+    fn fmt_range_day(&self, day: u32, width: usize) -> Option<String> {
+        let date = self.date(day);
+        if !self.in_range(Some(date))
+            || self
+                .day_categories(day)
+                .iter()
+                .any(|c| c.name() != Category::MARKED)
+        {
+            return None;
+        }
+
+        let position = RangePosition::new(
+            self.in_range(date.pred_opt()),
+            self.in_range(date.succ_opt()),
+        );
+        let continued =
+            position.has_previous() && (day == 1 || date.weekday() == self.begin_weekday);
+
+        Some(
+            self.range_marker
+                .decorate_range(&format!("{: ^2}", day), position, continued, width),
+        )
+    }
+
+    fn fmt_day(&self, day: u32) -> String {
+        let reference = self.reference(day);
+        let space = self
+            .day_width
+            .saturating_sub(reference.as_deref().map_or(0, display_width));
+        let today = self.today.as_ref().filter(|_| self.is_today(day));
+        if let Some(cell) = self.fmt_range_day(day, space).filter(|_| today.is_none()) {
+            return format!("{}{}", cell, reference.unwrap_or_default());
+        }
+
+        let text = match today {
+            // the highlight replaces the other decorations, as both would not fit in the cell
+            Some(today) => today.marker().decorate(&format!("{: ^2}", day)),
+            None => self.decorate_day(day),
         };
 
-        if self.year == year && self.month == date.month0() + 1 {
-            self.marked.insert(date.day());
+        match reference {
+            Some(reference) => center(
+                &format!("{}{}", truncate(&text, space), reference),
+                self.day_width,
+            ),
+            None => center(&text, self.day_width),
         }
     }
 
-    fn unmark(&mut self, date: NaiveDate) {
-        self.marked.remove(&date.day());
+    /// Day decorated by its categories, or by the weekend style
+    fn decorate_day(&self, day: u32) -> String {
+        let mut categories = self.day_categories(day);
+        if self.in_range(Some(self.date(day))) {
+            // the range is drawn by the other categories
+            categories.retain(|c| c.name() != Category::MARKED);
+        }
+
+        let weekend = self
+            .weekend
+            .as_ref()
+            .filter(|style| style.workweek().is_weekend(self.date(day).weekday()));
+
+        match weekend {
+            Some(style) if categories.is_empty() => {
+                style.marker().decorate(&format!("{: ^2}", day))
+            }
+            _ if categories.is_empty() => day.to_string(),
+            _ => {
+                let value = self
+                    .values
+                    .get(&day)
+                    .map(|v| (*v, self.sorted_values.as_slice()));
+
+                self.combination
+                    .decorate(&format!("{: ^2}", day), &categories, value)
+            }
+        }
     }
 
-    fn is_marked(&self, date: NaiveDate) -> bool {
-        self.marked.contains(&date.day())
+    fn sort_values(&mut self) {
+        self.sorted_values = self.values.values().copied().collect();
+        self.sorted_values.sort_by(f64::total_cmp);
     }
 
-    fn width(&self) -> usize {
-        self.day_width * 7
+    /// Footnote lines, wrapped once until the annotations change
+    fn footnote_lines(&self) -> Ref<'_, [String]> {
+        if self.footnote_lines.borrow().is_none() {
+            self.footnote_lines.replace(Some(self.wrap_footnotes()));
+        }
+
+        Ref::map(self.footnote_lines.borrow(), |lines| {
+            lines.as_deref().unwrap_or_default()
+        })
     }
 
-    fn height(&self) -> usize {
-        2 + self.weeks.len()
+    /// Footnote lines, wrapped to the width of the calendar
+    fn wrap_footnotes(&self) -> Vec<String> {
+        let Some(style) = self.footnotes else {
+            return vec![];
+        };
+
+        let width = self.width();
+        let mut lines = vec![];
+
+        for (n, notes) in self.annotations.values().enumerate() {
+            let reference = style.reference(n + 1);
+            let indent = display_width(&reference) + 1;
+
+            for (i, line) in wrap(&notes.join("; "), width.saturating_sub(indent))
+                .into_iter()
+                .enumerate()
+            {
+                let head = if i == 0 {
+                    format!("{} ", reference)
+                } else {
+                    " ".repeat(indent)
+                };
+                let line = format!("{}{}", head, line);
+                let space = width.saturating_sub(display_width(&line));
+                lines.push(format!("{}{}", line, " ".repeat(space)));
+            }
+        }
+
+        lines
     }
-}
 
-impl Display for MonthCalendar {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(
-            f,
-            "{: ^width$}",
-            Month::from_u32(self.month).unwrap().name(),
-            width = self.day_width * 7
-        )?;
+    /// Write the cell of the day, without allocating for days with nothing to decorate
+    fn write_day(&self, line: &mut String, day: u32) {
+        let plain = !self.marks.contains_key(&day)
+            && !self.in_store(day)
+            && self.ranges.is_empty()
+            && (self.footnotes.is_none() || !self.annotations.contains_key(&day))
+            && !self.is_today(day)
+            && !self
+                .weekend
+                .as_ref()
+                .is_some_and(|style| style.workweek().is_weekend(self.date(day).weekday()));
+
+        if plain {
+            // same as `{: ^width$}`
+            let digits = if day < 10 { 1 } else { 2 };
+            let space = self.day_width.saturating_sub(digits);
+            line.extend(std::iter::repeat_n(' ', space / 2));
+            if day >= 10 {
+                line.push(char::from(b'0' + (day / 10) as u8));
+            }
+            line.push(char::from(b'0' + (day % 10) as u8));
+            line.extend(std::iter::repeat_n(' ', space - space / 2));
+        } else {
+            line.push_str(&self.fmt_day(day));
+        }
+    }
+
+    fn fmt_week(&self, week: &RangeInclusive<u32>, cell: impl Fn(&mut String, u32)) -> String {
+        let blank = (7 - (week.end() - week.start() + 1) as usize) * self.day_width;
+        let mut line = String::with_capacity(self.day_width * 7);
+
+        if *week.start() == 1 {
+            line.extend(std::iter::repeat_n(' ', blank));
+        }
+
+        for day in week.clone() {
+            cell(&mut line, day);
+        }
+
+        if *week.start() != 1 {
+            line.extend(std::iter::repeat_n(' ', blank));
+        }
+
+        line
+    }
 
+    fn fmt_weekdays(&self) -> String {
+        let mut line = String::new();
         let mut weekday = self.begin_weekday;
         let weekday_width = if self.day_width > 4 { 3 } else { 2 };
+
         for _ in 0..7 {
-            write!(
-                f,
-                "{: ^width$}",
-                &weekday.to_string()[..weekday_width],
-                width = self.day_width
-            )?;
+            let label = &weekday.to_string()[..weekday_width];
+
+            match &self.weekend {
+                Some(style) if style.workweek().is_weekend(weekday) => {
+                    line.push_str(&center(&style.marker().decorate(label), self.day_width))
+                }
+                _ => line.push_str(&center(label, self.day_width)),
+            }
             weekday = weekday.succ();
         }
 
-        writeln!(f)?;
+        line
+    }
+}
 
-        let first_week = self.weeks.first().unwrap();
-        let last_week = self.weeks.last().unwrap();
+impl Calendar for MonthCalendar {
+    fn mark(&mut self, date: NaiveDate) {
+        self.insert_mark(date, 0);
+    }
 
-        for _ in 1..=(7 - first_week.end()) {
-            write!(f, "{: ^width$}", "", width = self.day_width)?;
+    fn unmark(&mut self, date: NaiveDate) {
+        self.cut_ranges(date..=date);
+        self.remove_mark(date, 0);
+        if self.contains(date) && self.values.remove(&date.day()).is_some() {
+            self.sort_values();
         }
+    }
 
-        let last_i = self.weeks.len() - 1;
-        for (i, week) in self.weeks[..].iter().cloned().enumerate() {
-            for day in week.into_iter() {
-                if self.marked.contains(&day) {
-                    if self.marked.contains(&day) {
-                        write!(
-                            f,
-                            "{: ^width$}",
-                            self.marker.decorate(&format!("{: ^2}", day)),
-                            width = self.day_width
-                        )?;
-                    } else {
-                        write!(f, "{: ^width$}", day, width = self.day_width)?;
-                    }
-                } else {
-                    write!(f, "{: ^width$}", day, width = self.day_width)?;
-                }
-            }
+    fn is_marked(&self, date: NaiveDate) -> bool {
+        self.contains(date)
+            && self
+                .marks
+                .get(&date.day())
+                .is_some_and(|indices| indices.contains(&0))
+            || self.contains(date) && self.in_store(date.day())
+    }
 
-            if i == last_i {
-                for _ in 0..(7 - (last_week.end() - last_week.start()) - 1) {
-                    write!(f, "{: ^width$}", "", width = self.day_width)?;
-                }
-            } else {
-                writeln!(f)?;
+    fn mark_range(&mut self, range: RangeInclusive<NaiveDate>) {
+        let Some(shown) = self.date_range() else {
+            return;
+        };
+        if range.end() < shown.start() || shown.end() < range.start() {
+            return;
+        }
+
+        for date in range.start().iter_days().take_while(|d| d <= range.end()) {
+            self.mark(date);
+        }
+        self.ranges.push(range);
+    }
+
+    fn set_range_marker(&mut self, marker: Rc<dyn RangeMarker>) {
+        self.range_marker = marker;
+    }
+
+    fn width(&self) -> usize {
+        self.day_width * 7
+    }
+
+    fn draw(&self, region: &mut Region<'_>) {
+        let width = self.width();
+        let alignment = self.header.alignment();
+
+        region.write(0, &alignment.align(&self.header.title(self.date(1)), width));
+        let mut row = 1;
+
+        if let Some(subtitle) = self.header.subtitle() {
+            region.write(row, &alignment.align(subtitle, width));
+            row += 1;
+        }
+        if self.header.has_weekdays() {
+            region.write(row, &self.fmt_weekdays());
+            row += 1;
+        }
+
+        for week in &self.weeks {
+            region.write(
+                row,
+                &self.fmt_week(week, |line, day| self.write_day(line, day)),
+            );
+            row += 1;
+
+            if let Some(label) = &self.day_label {
+                let line = self.fmt_week(week, |line, day| {
+                    line.push_str(&center(
+                        &label.label(self.date(day)).unwrap_or_default(),
+                        self.day_width,
+                    ))
+                });
+                region.write(row, &line);
+                row += 1;
             }
         }
 
-        Ok(())
+        if self.footnotes.is_none() {
+            return;
+        }
+        for line in self.footnote_lines().iter() {
+            region.write(row, line);
+            row += 1;
+        }
+    }
+
+    fn height(&self) -> usize {
+        let week_height = if self.day_label.is_some() { 2 } else { 1 };
+
+        self.header.height() + self.weeks.len() * week_height + self.footnote_lines().len()
+    }
+
+    fn date_range(&self) -> Option<RangeInclusive<NaiveDate>> {
+        let last_day = *self.weeks.last()?.end();
+
+        Some(self.date(1)..=self.date(last_day))
+    }
+
+    fn annotate(&mut self, date: NaiveDate, text: &str) {
+        if self.contains(date) {
+            self.annotations
+                .entry(date.day())
+                .or_default()
+                .push(text.to_string());
+            self.footnote_lines.take();
+        }
+    }
+
+    fn remove_annotations(&mut self, date: NaiveDate) {
+        if self.contains(date) {
+            self.annotations.remove(&date.day());
+            self.footnote_lines.take();
+        }
+    }
+
+    fn annotations(&self, date: NaiveDate) -> Vec<String> {
+        if !self.contains(date) {
+            return vec![];
+        }
+
+        self.annotations
+            .get(&date.day())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn mark_holiday(&mut self, holiday: &Holiday) {
+        if self.contains(holiday.date) {
+            self.holidays
+                .entry(holiday.date.day())
+                .or_default()
+                .push(holiday.name.clone());
+            self.insert_mark(holiday.date, 1);
+        }
+    }
+
+    fn holidays(&self) -> Vec<Holiday> {
+        self.holidays
+            .iter()
+            .flat_map(|(day, names)| {
+                names
+                    .iter()
+                    .map(|name| Holiday::new(self.date(*day), name.clone()))
+            })
+            .collect()
+    }
+
+    fn set_holiday_marker(&mut self, marker: Rc<dyn Marker>) {
+        self.categories[1] = Category::from_rc(Category::HOLIDAY, marker)
+            .with_priority(self.categories[1].priority());
+    }
+
+    fn mark_category(&mut self, date: NaiveDate, category: &Category) {
+        if self.contains(date) {
+            let index = self.register(category);
+            self.insert_mark(date, index);
+        }
+    }
+
+    fn unmark_category(&mut self, date: NaiveDate, name: &str) {
+        if let Some(index) = self.categories.iter().position(|c| c.name() == name) {
+            self.remove_mark(date, index);
+        }
+        if name == Category::MARKED {
+            self.cut_ranges(date..=date);
+        }
+        if name == Category::HOLIDAY && self.contains(date) {
+            self.holidays.remove(&date.day());
+        }
+    }
+
+    fn categories_of(&self, date: NaiveDate) -> Vec<Category> {
+        if !self.contains(date) {
+            return vec![];
+        }
+
+        self.day_categories(date.day())
+            .into_iter()
+            .cloned()
+            .collect()
+    }
+
+    fn categories(&self) -> Vec<Category> {
+        let mut used: BTreeSet<usize> = self.marks.values().flatten().copied().collect();
+        let stored = self.store.as_ref().is_some_and(|store| {
+            let last = self.weeks.last().map_or(1, |week| *week.end());
+            !store
+                .marked_between(self.date(1)..=self.date(last))
+                .is_empty()
+        });
+        if stored {
+            used.insert(0);
+        }
+
+        let mut categories: Vec<(usize, &Category)> =
+            used.into_iter().map(|i| (i, &self.categories[i])).collect();
+        categories.sort_by_key(|(i, c)| (Reverse(c.priority()), *i));
+
+        categories.into_iter().map(|(_, c)| c.clone()).collect()
+    }
+
+    fn set_combination(&mut self, combination: MarkerCombination) {
+        self.combination = combination;
+    }
+
+    /// Also marks the date, so that the value is drawn by the marker of `mark`
+    fn set_value(&mut self, date: NaiveDate, value: f64) {
+        if self.contains(date) {
+            self.values.insert(date.day(), value);
+            self.sort_values();
+            self.mark(date);
+        }
+    }
+
+    fn value(&self, date: NaiveDate) -> Option<f64> {
+        if !self.contains(date) {
+            return None;
+        }
+
+        self.values.get(&date.day()).copied()
+    }
+
+    fn set_today_highlight(&mut self, highlight: Option<TodayHighlight>) {
+        self.today = highlight;
+    }
+
+    fn set_mark_store(&mut self, store: Option<Rc<dyn MarkStore>>) {
+        self.store = store;
+    }
+
+    fn set_weekend_style(&mut self, style: Option<WeekendStyle>) {
+        self.weekend = style;
+    }
+}
+
+impl Display for MonthCalendar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.render(f)
     }
 }
 
@@ -165,6 +706,11 @@ pub fn str_month_calendar(year: i32, month: u32) -> Option<String> {
 
 #[cfg(test)]
 mod tests {
+    use crate::{
+        n_date, Alignment, Calendars, FixedClock, Scale, ScaleMarker, TitleFormat, UnderlineRange,
+        Workweek,
+    };
+
     use super::*;
 
     #[test]
@@ -181,4 +727,244 @@ mod tests {
             MonthCalendar::new(2024, 6, Weekday::Sun, 4, BasicMarker::SquareBrackets).unwrap();
         println!("{}", display_test);
     }
+
+    #[test]
+    fn test_categories() {
+        let on_call = Category::new("on-call", BasicMarker::Char('!'));
+        let release = Category::new("release", BasicMarker::SquareBrackets).with_priority(1);
+
+        let mut cal = MonthCalendar::from_ym(2024, 6).unwrap();
+        cal.mark_category(n_date!(2024, 6, 3), &on_call);
+        cal.mark_category(n_date!(2024, 6, 4), &on_call);
+        cal.mark_category(n_date!(2024, 6, 4), &release);
+        cal.mark(n_date!(2024, 6, 5));
+        cal.mark_category(n_date!(2024, 6, 5), &on_call);
+
+        let names = |cal: &MonthCalendar, date| -> Vec<String> {
+            cal.categories_of(date)
+                .iter()
+                .map(|c| c.name().to_string())
+                .collect()
+        };
+        assert_eq!(names(&cal, n_date!(2024, 6, 4)), ["release", "on-call"]);
+        assert_eq!(
+            names(&cal, n_date!(2024, 6, 5)),
+            [Category::MARKED, "on-call"]
+        );
+        assert!(cal.to_string().contains("!3 ![4 ][5 ]"));
+
+        cal.set_combination(MarkerCombination::Marker(Rc::new(BasicMarker::Char('+'))));
+        assert!(cal.to_string().contains("!3 !+4 ++5 +"));
+
+        cal.unmark_category(n_date!(2024, 6, 4), "release");
+        assert_eq!(names(&cal, n_date!(2024, 6, 4)), ["on-call"]);
+        assert!(!cal.is_marked(n_date!(2024, 6, 4)));
+        assert!(cal.is_marked(n_date!(2024, 6, 5)));
+
+        let lowest = Category::new("lowest", BasicMarker::None).with_priority(i32::MIN);
+        cal.mark_category(n_date!(2024, 6, 5), &lowest);
+        assert_eq!(
+            names(&cal, n_date!(2024, 6, 5)),
+            [Category::MARKED, "on-call", "lowest"]
+        );
+
+        cal.mark_holiday(&Holiday::new(n_date!(2024, 6, 19), "Juneteenth"));
+        cal.unmark_category(n_date!(2024, 6, 19), Category::HOLIDAY);
+        assert!(cal.holidays().is_empty());
+
+        // the built-in categories cannot be redefined
+        cal.mark_holiday(&Holiday::new(n_date!(2024, 6, 19), "Juneteenth"));
+        cal.mark_category(
+            n_date!(2024, 6, 20),
+            &Category::new(Category::HOLIDAY, BasicMarker::Char('#')).with_priority(9),
+        );
+        let holiday = &cal.categories_of(n_date!(2024, 6, 19))[0];
+        assert_eq!(holiday.priority(), -1);
+        assert!(cal.to_string().contains("*19**20*"));
+    }
+
+    #[test]
+    fn test_footnotes() {
+        let mut cal = MonthCalendar::from_ym(2024, 6).unwrap();
+        cal.mark(n_date!(2024, 6, 14));
+        cal.annotate(n_date!(2024, 6, 14), "v2.3 release");
+        cal.annotate(n_date!(2024, 6, 3), "Alice OOO until the end of the week");
+        assert_eq!(cal.height(), 8);
+
+        cal.set_footnotes(FootnoteStyle::Numbers);
+        let output = cal.to_string();
+        let lines: Vec<&str> = output.split('\n').collect();
+
+        assert_eq!(lines.len(), cal.height());
+        assert!(lines.iter().all(|line| display_width(line) == cal.width()));
+        assert_eq!(lines[3], " 2   3¹  4   5   6   7   8  ");
+        assert_eq!(lines[4], " 9   10  11  12  13 [14² 15 ");
+        assert_eq!(lines[8], "¹ Alice OOO until the end of");
+        assert_eq!(lines[9], "  the week                  ");
+        assert_eq!(lines[10], "² v2.3 release              ");
+
+        // the wrapped footnotes follow later changes
+        cal.remove_annotations(n_date!(2024, 6, 3));
+        assert_eq!(cal.height(), 9);
+        assert!(cal.to_string().ends_with("\n¹ v2.3 release              "));
+    }
+
+    #[test]
+    fn test_range() {
+        let mut jan = MonthCalendar::from_ym(2024, 1).unwrap();
+        let mut feb = MonthCalendar::from_ym(2024, 2).unwrap();
+        for cal in [&mut jan, &mut feb] {
+            cal.mark_range(n_date!(2024, 1, 3)..=n_date!(2024, 1, 5));
+            cal.mark_range(n_date!(2024, 1, 30)..=n_date!(2024, 2, 6));
+        }
+        feb.unmark(n_date!(2024, 2, 4));
+
+        let jan = jan.to_string();
+        let jan: Vec<&str> = jan.split('\n').collect();
+        assert_eq!(jan[2], "     1   2  [3 - 4 - 5 ] 6  ");
+        assert_eq!(jan[6], " 28  29 [30- 31-            ");
+
+        let feb = feb.to_string();
+        let feb: Vec<&str> = feb.split('\n').collect();
+        assert_eq!(feb[2], "                -1 - 2 - 3 ]");
+        assert_eq!(feb[3], " 4  [5 - 6 ] 7   8   9   10 ");
+
+        let mut cal = MonthCalendar::from_ym(2024, 6).unwrap();
+        cal.mark_range(n_date!(2024, 6, 3)..=n_date!(2024, 6, 14));
+        cal.mark_holiday(&Holiday::new(n_date!(2024, 6, 5), "Holiday"));
+        cal.unmark_range(n_date!(2024, 6, 10)..=n_date!(2024, 6, 11));
+        assert_eq!(
+            cal.ranges,
+            [
+                n_date!(2024, 6, 3)..=n_date!(2024, 6, 9),
+                n_date!(2024, 6, 12)..=n_date!(2024, 6, 14)
+            ]
+        );
+        cal.mark(n_date!(2024, 6, 10));
+
+        let output = cal.to_string();
+        let lines: Vec<&str> = output.split('\n').collect();
+        assert_eq!(lines[3], " 2  [3 - 4 -*5 * 6 - 7 - 8 -");
+        assert_eq!(lines[4], "-9 ][10] 11 [12- 13- 14] 15 ");
+
+        cal.set_range_marker(Rc::new(UnderlineRange));
+        cal.annotate(n_date!(2024, 6, 13), "release");
+        cal.set_footnotes(FootnoteStyle::Numbers);
+        let output = cal.to_string();
+        let week = output.split('\n').nth(4).unwrap();
+        assert_eq!(display_width(week), cal.width());
+        assert!(week.ends_with("\x1b[0m  15 "));
+        assert!(week.contains("\x1b[0m¹"));
+    }
+
+    #[test]
+    fn test_values() {
+        let mut cal = MonthCalendar::new(
+            2024,
+            6,
+            Weekday::Sun,
+            4,
+            ScaleMarker::chars(Scale::Thresholds(vec![2.0, 5.0]), "·*#"),
+        )
+        .unwrap();
+        cal.set_value(n_date!(2024, 6, 3), 1.0);
+        cal.set_value(n_date!(2024, 6, 4), 3.0);
+        cal.set_value(n_date!(2024, 6, 5), 8.0);
+        cal.mark(n_date!(2024, 6, 6));
+
+        assert!(cal.to_string().contains("·3 ·*4 *#5 ##6 #"));
+        assert_eq!(cal.value(n_date!(2024, 6, 4)), Some(3.0));
+
+        cal.unmark(n_date!(2024, 6, 4));
+        assert_eq!(cal.value(n_date!(2024, 6, 4)), None);
+
+        let mut cal = MonthCalendar::builder()
+            .year(2024)
+            .month(6)
+            .day_width(6)
+            .combination(MarkerCombination::Nested)
+            .build()
+            .unwrap();
+        let deploys = Category::new("deploys", ScaleMarker::chars(Scale::Quantiles, "·*#"));
+        cal.mark_category(n_date!(2024, 6, 3), &deploys);
+        cal.mark_category(n_date!(2024, 6, 5), &deploys);
+        for (day, value) in [(3, 1.0), (4, 2.0), (5, 3.0)] {
+            cal.set_value(n_date!(2024, 6, day), value);
+        }
+        assert!(cal.to_string().contains("·[3 ]· [4 ] #[5 ]#"));
+    }
+
+    #[test]
+    fn test_today() {
+        let mut cal = MonthCalendar::from_ym(2024, 6).unwrap();
+        cal.mark(n_date!(2024, 6, 4));
+        cal.set_today_highlight(Some(
+            TodayHighlight::new(BasicMarker::Char('>')).with_clock(FixedClock(n_date!(2024, 6, 3))),
+        ));
+
+        assert!(cal.to_string().contains(" 2  >3 >[4 ]"));
+        assert!(!cal.is_marked(n_date!(2024, 6, 3)));
+
+        cal.set_today_highlight(Some(
+            TodayHighlight::new(BasicMarker::Char('>')).with_clock(FixedClock(n_date!(2024, 6, 4))),
+        ));
+        cal.set_weekend_style(Some(WeekendStyle::new(
+            Workweek::default(),
+            BasicMarker::UnderScore,
+        )));
+        let output = cal.to_string();
+        assert!(output.contains("_2 _ 3  >4 > 5 "));
+        assert!(output
+            .split('\n')
+            .all(|line| display_width(line) == cal.width()));
+
+        // today on a weekend
+        cal.set_today_highlight(Some(
+            TodayHighlight::new(BasicMarker::Char('>')).with_clock(FixedClock(n_date!(2024, 6, 8))),
+        ));
+        let output = cal.to_string();
+        assert!(output.contains(">8 >"));
+        assert!(output
+            .split('\n')
+            .all(|line| display_width(line) == cal.width()));
+    }
+
+    #[test]
+    fn test_header() {
+        let mut cal = MonthCalendar::from_ym(2024, 6).unwrap();
+        cal.set_header(
+            Header::new(TitleFormat::MonthYear)
+                .with_alignment(Alignment::Left)
+                .with_subtitle("Platform team")
+                .without_weekdays(),
+        );
+
+        let output = cal.to_string();
+        let lines: Vec<&str> = output.split('\n').collect();
+        assert_eq!(lines.len(), cal.height());
+        assert_eq!(lines[0].trim_end(), "June 2024");
+        assert_eq!(lines[1].trim_end(), "Platform team");
+        assert_eq!(lines[2].trim(), "1");
+
+        cal.set_header(
+            Header::new(TitleFormat::custom(|d| d.format("%y/%m").to_string()))
+                .with_alignment(Alignment::Right),
+        );
+        assert!(cal
+            .to_string()
+            .starts_with(&format!("{}24/06\n", " ".repeat(23))));
+
+        let calendars = Calendars::new(
+            vec![
+                Box::new(cal),
+                Box::new(MonthCalendar::from_ym(2024, 7).unwrap()),
+            ],
+            "".to_string(),
+            2,
+        );
+        assert_eq!(
+            calendars.to_string().split('\n').count(),
+            calendars.height()
+        );
+    }
 }
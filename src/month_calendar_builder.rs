@@ -0,0 +1,351 @@
+use std::rc::Rc;
+
+use chrono::Weekday;
+
+use crate::{
+    utils::display_width, BasicMarker, Calendar, Category, DayLabel, Error, FootnoteStyle, Header,
+    MarkStore, Marker, MarkerCombination, MonthCalendar, RangeMarker, RangePosition,
+    TodayHighlight, WeekendStyle,
+};
+
+/// Builder of [`MonthCalendar`], with the defaults of [`MonthCalendar::from_ym`]
+///
+/// ```
+/// use chrono::Weekday;
+/// use text_calendar::{BasicMarker, MonthCalendar};
+///
+/// let calendar = MonthCalendar::builder()
+///     .year(2024)
+///     .month(6)
+///     .begin_weekday(Weekday::Mon)
+///     .marker(BasicMarker::Char('*'))
+///     .build()
+///     .unwrap();
+///
+/// // "[31]" does not fit in 3 columns
+/// assert!(MonthCalendar::builder().year(2024).month(6).day_width(3).build().is_err());
+/// ```
+/// Without a year and a month, it can be used as the template of a
+/// [`YearCalendar`](crate::YearCalendar).
+#[derive(Debug, Clone)]
+pub struct MonthCalendarBuilder {
+    year: Option<i32>,
+    month: Option<u32>,
+    begin_weekday: Weekday,
+    day_width: usize,
+    marker: Rc<dyn Marker>,
+    day_label: Option<Rc<dyn DayLabel>>,
+    footnotes: Option<FootnoteStyle>,
+    header: Header,
+    range_marker: Option<Rc<dyn RangeMarker>>,
+    combination: Option<MarkerCombination>,
+    today: Option<TodayHighlight>,
+    weekend: Option<WeekendStyle>,
+    store: Option<Rc<dyn MarkStore>>,
+}
+
+impl Default for MonthCalendarBuilder {
+    fn default() -> Self {
+        Self {
+            year: None,
+            month: None,
+            begin_weekday: Weekday::Sun,
+            day_width: 4,
+            marker: Rc::new(BasicMarker::SquareBrackets),
+            day_label: None,
+            footnotes: None,
+            header: Header::default(),
+            range_marker: None,
+            combination: None,
+            today: None,
+            weekend: None,
+            store: None,
+        }
+    }
+}
+
+impl MonthCalendarBuilder {
+    pub fn year(mut self, year: i32) -> Self {
+        self.year = Some(year);
+        self
+    }
+
+    /// Month (1-12)
+    pub fn month(mut self, month: u32) -> Self {
+        self.month = Some(month);
+        self
+    }
+
+    pub fn begin_weekday(mut self, weekday: Weekday) -> Self {
+        self.begin_weekday = weekday;
+        self
+    }
+
+    pub fn day_width(mut self, width: usize) -> Self {
+        self.day_width = width;
+        self
+    }
+
+    pub fn marker(mut self, marker: impl Marker + 'static) -> Self {
+        self.marker = Rc::new(marker);
+        self
+    }
+
+    pub fn day_label(mut self, label: impl DayLabel + 'static) -> Self {
+        self.day_label = Some(Rc::new(label));
+        self
+    }
+
+    pub fn footnotes(mut self, style: FootnoteStyle) -> Self {
+        self.footnotes = Some(style);
+        self
+    }
+
+    pub fn header(mut self, header: Header) -> Self {
+        self.header = header;
+        self
+    }
+
+    pub fn range_marker(mut self, marker: impl RangeMarker + 'static) -> Self {
+        self.range_marker = Some(Rc::new(marker));
+        self
+    }
+
+    pub fn combination(mut self, combination: MarkerCombination) -> Self {
+        self.combination = Some(combination);
+        self
+    }
+
+    pub fn today_highlight(mut self, highlight: TodayHighlight) -> Self {
+        self.today = Some(highlight);
+        self
+    }
+
+    pub fn weekend_style(mut self, style: WeekendStyle) -> Self {
+        self.weekend = Some(style);
+        self
+    }
+
+    pub fn mark_store(mut self, store: Rc<dyn MarkStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Check the options and build the calendar
+    pub fn build(&self) -> Result<MonthCalendar, Error> {
+        let (Some(year), Some(month)) = (self.year, self.month) else {
+            return Err(invalid("the year and the month are required"));
+        };
+        self.validate()?;
+
+        let mut calendar = MonthCalendar::with_marker(
+            year,
+            month,
+            self.begin_weekday,
+            self.day_width,
+            self.marker.clone(),
+        )
+        .ok_or_else(|| invalid(&format!("no month {} in {}", month, year)))?;
+
+        if let Some(label) = &self.day_label {
+            calendar.set_day_label_rc(label.clone());
+        }
+        if let Some(style) = self.footnotes {
+            calendar.set_footnotes(style);
+        }
+        calendar.set_header(self.header.clone());
+        if let Some(marker) = &self.range_marker {
+            calendar.set_range_marker(marker.clone());
+        }
+        if let Some(combination) = &self.combination {
+            calendar.set_combination(combination.clone());
+        }
+        calendar.set_today_highlight(self.today.clone());
+        calendar.set_weekend_style(self.weekend.clone());
+        calendar.set_mark_store(self.store.clone());
+
+        Ok(calendar)
+    }
+
+    pub(crate) fn configured_day_width(&self) -> usize {
+        self.day_width
+    }
+
+    /// Build the calendar of another month with the same options
+    pub(crate) fn build_month(&self, year: i32, month: u32) -> Result<MonthCalendar, Error> {
+        self.clone().year(year).month(month).build()
+    }
+
+    /// Check that the day width fits the weekday labels and the decorated days
+    ///
+    /// Each day is decorated as it is drawn: the marker combined with the holiday marker,
+    /// the today and weekend markers, every position of the range marker, and the
+    /// reference of the first footnote after them.
+    fn validate(&self) -> Result<(), Error> {
+        let weekday_width = if self.day_width > 4 { 3 } else { 2 };
+        if self.day_width < weekday_width {
+            return Err(invalid(&format!(
+                "day width {} is narrower than the weekday labels ({})",
+                self.day_width, weekday_width
+            )));
+        }
+
+        let reference = self.footnotes.map(|style| style.reference(1));
+        let reference_width = reference.as_deref().map_or(0, display_width);
+        let space = self.day_width.saturating_sub(reference_width);
+
+        let marked = Category::from_rc(Category::MARKED, self.marker.clone());
+        let holiday = Category::holiday();
+        let combination = self.combination.clone().unwrap_or_default();
+        let mut cells = vec![(
+            match combination {
+                MarkerCombination::Priority => "marker",
+                _ => "combined markers",
+            },
+            combination.decorate("31", &[&marked, &holiday], None),
+        )];
+        if let Some(today) = &self.today {
+            cells.push(("today marker", today.marker().decorate("31")));
+        }
+        if let Some(weekend) = &self.weekend {
+            cells.push(("weekend marker", weekend.marker().decorate("31")));
+        }
+        if let Some(marker) = &self.range_marker {
+            let positions = [
+                RangePosition::Single,
+                RangePosition::Start,
+                RangePosition::Middle,
+                RangePosition::End,
+            ];
+            for position in positions {
+                for continued in [false, true] {
+                    let cell = marker.decorate_range("31", position, continued, space);
+                    cells.push(("range marker", cell));
+                }
+            }
+        }
+
+        for (name, cell) in cells {
+            let width = display_width(&cell) + reference_width;
+            if self.day_width < width {
+                return Err(invalid(&format!(
+                    "day width {} is narrower than the {} ({})",
+                    self.day_width, name, width
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn invalid(message: &str) -> Error {
+    Error::InvalidMonthCalendar(message.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{n_date, UnderlineRange, Workweek, YearCalendar};
+
+    use super::*;
+
+    /// Range marker that ignores the width
+    struct WideRange;
+
+    impl RangeMarker for WideRange {
+        fn decorate_range(&self, day: &str, _: RangePosition, _: bool, _: usize) -> String {
+            format!("<<{}>>", day)
+        }
+    }
+
+    #[test]
+    fn test() {
+        let calendar = MonthCalendar::builder()
+            .year(2024)
+            .month(6)
+            .build()
+            .unwrap();
+        assert_eq!(
+            calendar.to_string(),
+            MonthCalendar::from_ym(2024, 6).unwrap().to_string()
+        );
+
+        assert!(MonthCalendar::builder().month(6).build().is_err());
+        assert!(MonthCalendar::builder()
+            .year(2024)
+            .month(13)
+            .build()
+            .is_err());
+        assert_eq!(
+            MonthCalendar::builder()
+                .year(2024)
+                .month(6)
+                .day_width(1)
+                .marker(BasicMarker::None)
+                .build()
+                .unwrap_err()
+                .to_string(),
+            "invalid month calendar: day width 1 is narrower than the weekday labels (2)"
+        );
+        assert!(MonthCalendar::builder()
+            .year(2024)
+            .month(6)
+            .day_width(3)
+            .marker(BasicMarker::None)
+            .build()
+            .is_ok());
+
+        // the widths of the composed cells
+        let june = MonthCalendar::builder().year(2024).month(6);
+        assert_eq!(
+            june.clone()
+                .combination(MarkerCombination::Nested)
+                .build()
+                .unwrap_err()
+                .to_string(),
+            "invalid month calendar: day width 4 is narrower than the combined markers (6)"
+        );
+        assert!(june
+            .clone()
+            .day_width(6)
+            .combination(MarkerCombination::Nested)
+            .build()
+            .is_ok());
+        assert_eq!(
+            june.clone()
+                .footnotes(FootnoteStyle::Numbers)
+                .build()
+                .unwrap_err()
+                .to_string(),
+            "invalid month calendar: day width 4 is narrower than the marker (5)"
+        );
+        assert!(june
+            .clone()
+            .day_width(5)
+            .footnotes(FootnoteStyle::Numbers)
+            .range_marker(UnderlineRange)
+            .build()
+            .is_ok());
+        assert_eq!(
+            june.range_marker(WideRange)
+                .build()
+                .unwrap_err()
+                .to_string(),
+            "invalid month calendar: day width 4 is narrower than the range marker (6)"
+        );
+
+        let template = MonthCalendar::builder()
+            .begin_weekday(Weekday::Mon)
+            .weekend_style(WeekendStyle::new(
+                Workweek::default(),
+                BasicMarker::UnderScore,
+            ));
+        let mut year = YearCalendar::from_builder(2024, &template).unwrap();
+        year.mark(n_date!(2024, 6, 3));
+        assert!(year.to_string().contains(" Mo  Tu  We  Th  Fr _Sa__Su_"));
+        assert!(year.to_string().contains("[3 ]"));
+
+        let narrow = template.day_width(3);
+        assert!(YearCalendar::from_builder(2024, &narrow).is_err());
+    }
+}
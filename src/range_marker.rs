@@ -0,0 +1,136 @@
+use std::fmt::Debug;
+
+use crate::utils::center;
+
+/// Place of a day in a marked range
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangePosition {
+    /// The range is one day long
+    Single,
+    /// First day of the range
+    Start,
+    /// Day between the first and the last one
+    Middle,
+    /// Last day of the range
+    End,
+}
+
+impl RangePosition {
+    pub fn new(has_previous: bool, has_next: bool) -> Self {
+        match (has_previous, has_next) {
+            (false, false) => RangePosition::Single,
+            (false, true) => RangePosition::Start,
+            (true, true) => RangePosition::Middle,
+            (true, false) => RangePosition::End,
+        }
+    }
+
+    /// Whether the range goes on after this day
+    pub fn has_next(&self) -> bool {
+        matches!(self, RangePosition::Start | RangePosition::Middle)
+    }
+
+    /// Whether the range began before this day
+    pub fn has_previous(&self) -> bool {
+        matches!(self, RangePosition::Middle | RangePosition::End)
+    }
+}
+
+/// Marker for days in a range, drawing whole cells so that neighbors connect
+pub trait RangeMarker {
+    /// Draw a cell of `width` columns
+    ///
+    /// `day` is 2 columns wide. `continued` is true when the range goes on from
+    /// the previous week row or the previous month.
+    fn decorate_range(
+        &self,
+        day: &str,
+        position: RangePosition,
+        continued: bool,
+        width: usize,
+    ) -> String;
+}
+
+impl Debug for dyn RangeMarker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.decorate_range("  ", RangePosition::Single, false, 4)
+        )
+    }
+}
+
+/// Brackets around the range with connectors between days: `[3 - 4 - 5 ]`
+#[derive(Debug, Clone)]
+pub struct BracketRange {
+    pub open: char,
+    pub close: char,
+    pub connector: char,
+}
+
+impl Default for BracketRange {
+    fn default() -> Self {
+        Self {
+            open: '[',
+            close: ']',
+            connector: '-',
+        }
+    }
+}
+
+impl RangeMarker for BracketRange {
+    fn decorate_range(
+        &self,
+        day: &str,
+        position: RangePosition,
+        continued: bool,
+        width: usize,
+    ) -> String {
+        if width < 4 {
+            return center(day, width);
+        }
+
+        let left = match position {
+            RangePosition::Single | RangePosition::Start => self.open,
+            _ if continued => self.connector,
+            _ => ' ',
+        };
+        let right = if position.has_next() {
+            self.connector
+        } else {
+            self.close
+        };
+
+        format!("{}{}{}", left, center(day, width - 2), right)
+    }
+}
+
+/// Continuous underline below the whole range (ANSI escape codes)
+#[derive(Debug, Clone, Default)]
+pub struct UnderlineRange;
+
+impl RangeMarker for UnderlineRange {
+    fn decorate_range(
+        &self,
+        day: &str,
+        position: RangePosition,
+        _continued: bool,
+        width: usize,
+    ) -> String {
+        if width < 4 {
+            return format!("\x1b[4m{}\x1b[0m", center(day, width));
+        }
+
+        let inner = center(day, width - 2);
+        let left = if position.has_previous() { " " } else { "" };
+        let right = if position.has_next() { " " } else { "" };
+        let outer_left = if left.is_empty() { " " } else { "" };
+        let outer_right = if right.is_empty() { " " } else { "" };
+
+        format!(
+            "{}\x1b[4m{}{}{}\x1b[0m{}",
+            outer_left, left, inner, right, outer_right
+        )
+    }
+}
@@ -0,0 +1,753 @@
+use std::{collections::BTreeSet, fmt::Display, ops::RangeInclusive, str::FromStr};
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+use crate::{Calendar, Category, Error};
+
+/// FREQ of a recurrence rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// BYDAY entry, e.g. `TU`, `2MO` or `-1FR`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByDay {
+    /// n-th weekday in the month (or year), counted from the end when negative
+    pub n: Option<i32>,
+    pub weekday: Weekday,
+}
+
+/// Recurrence rule (subset of RFC 5545 RRULE)
+///
+/// Supports `FREQ`, `INTERVAL`, `BYDAY` (with ordinals), `BYMONTHDAY`, `BYMONTH`,
+/// `COUNT`, `UNTIL` and `WKST`.
+///
+/// ```
+/// use text_calendar::RRule;
+///
+/// let every_other_tuesday: RRule = "FREQ=WEEKLY;INTERVAL=2;BYDAY=TU".parse().unwrap();
+/// let last_friday: RRule = "FREQ=MONTHLY;BYDAY=-1FR".parse().unwrap();
+/// let mid_and_end: RRule = "FREQ=MONTHLY;BYMONTHDAY=15,-1".parse().unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RRule {
+    pub frequency: Frequency,
+    pub interval: u32,
+    pub by_day: Vec<ByDay>,
+    pub by_month_day: Vec<i32>,
+    pub by_month: Vec<u32>,
+    pub count: Option<usize>,
+    pub until: Option<NaiveDate>,
+    pub week_start: Weekday,
+}
+
+impl RRule {
+    pub fn new(frequency: Frequency) -> Self {
+        Self {
+            frequency,
+            interval: 1,
+            by_day: vec![],
+            by_month_day: vec![],
+            by_month: vec![],
+            count: None,
+            until: None,
+            week_start: Weekday::Mon,
+        }
+    }
+
+    /// Occurrences from `start` up to `end` (inclusive)
+    ///
+    /// As in RFC 5545, `start` (DTSTART) is always the first occurrence and counts
+    /// toward `COUNT`, even when it does not match the rule.
+    pub fn dates(&self, start: NaiveDate, end: NaiveDate) -> Vec<NaiveDate> {
+        let mut dates = vec![];
+        let last = match self.until {
+            Some(until) => until.min(end),
+            None => end,
+        };
+        if start > last || self.count == Some(0) {
+            return dates;
+        }
+        dates.push(start);
+
+        // the Gregorian calendar repeats every 400 years, so a rule without any date in
+        // that many periods never matches
+        let cycle = match self.frequency {
+            Frequency::Daily => 146_097,
+            Frequency::Weekly => 20_871,
+            Frequency::Monthly => 4_800,
+            Frequency::Yearly => 400,
+        };
+        let mut empty = 0;
+
+        for k in 0.. {
+            let Some((period_start, candidates)) = self.period(start, k * self.interval as i64)
+            else {
+                break;
+            };
+            if period_start > last || empty >= cycle {
+                break;
+            }
+
+            empty += 1;
+            for date in candidates {
+                if date <= start {
+                    continue;
+                }
+                if date > last || self.count.is_some_and(|count| dates.len() >= count) {
+                    return dates;
+                }
+                dates.push(date);
+                empty = 0;
+            }
+        }
+
+        dates
+    }
+
+    /// First day of the `offset`-th period after `start` and the dates in it
+    fn period(&self, start: NaiveDate, offset: i64) -> Option<(NaiveDate, Vec<NaiveDate>)> {
+        let (period_start, mut candidates) = match self.frequency {
+            Frequency::Daily => {
+                let date = start.checked_add_signed(Duration::days(offset))?;
+                (date, vec![date])
+            }
+            Frequency::Weekly => {
+                let back = (start.weekday().num_days_from_monday() + 7
+                    - self.week_start.num_days_from_monday())
+                    % 7;
+                let week = start.checked_add_signed(Duration::days(offset * 7 - back as i64))?;
+                let days: Vec<NaiveDate> = week.iter_days().take(7).collect();
+
+                let candidates = if self.by_day.is_empty() {
+                    days.into_iter()
+                        .filter(|d| d.weekday() == start.weekday())
+                        .collect()
+                } else {
+                    days.into_iter()
+                        .filter(|d| self.by_day.iter().any(|b| b.weekday == d.weekday()))
+                        .collect()
+                };
+                (week, candidates)
+            }
+            Frequency::Monthly => {
+                let months = start.year() as i64 * 12 + start.month0() as i64 + offset;
+                let first = NaiveDate::from_ymd_opt(
+                    i32::try_from(months.div_euclid(12)).ok()?,
+                    months.rem_euclid(12) as u32 + 1,
+                    1,
+                )?;
+
+                (first, self.month_candidates(first, start))
+            }
+            Frequency::Yearly => {
+                let year = start.year().checked_add(i32::try_from(offset).ok()?)?;
+                let first = NaiveDate::from_ymd_opt(year, 1, 1)?;
+
+                (first, self.year_candidates(year, start))
+            }
+        };
+
+        candidates.retain(|d| self.matches_limits(*d));
+        candidates.sort();
+        candidates.dedup();
+
+        Some((period_start, candidates))
+    }
+
+    fn month_candidates(&self, first: NaiveDate, start: NaiveDate) -> Vec<NaiveDate> {
+        let days = month_days(first);
+
+        if !self.by_month_day.is_empty() {
+            resolve_month_days(&days, &self.by_month_day)
+        } else if !self.by_day.is_empty() {
+            nth_weekdays(&days, &self.by_day)
+        } else {
+            days.into_iter()
+                .filter(|d| d.day() == start.day())
+                .collect()
+        }
+    }
+
+    fn year_candidates(&self, year: i32, start: NaiveDate) -> Vec<NaiveDate> {
+        let months: Vec<NaiveDate> = if !self.by_month.is_empty() {
+            self.by_month
+                .iter()
+                .filter_map(|m| NaiveDate::from_ymd_opt(year, *m, 1))
+                .collect()
+        } else if !self.by_month_day.is_empty() {
+            (1..=12)
+                .filter_map(|m| NaiveDate::from_ymd_opt(year, m, 1))
+                .collect()
+        } else if !self.by_day.is_empty() {
+            // BYDAY over the whole year
+            let days: Vec<NaiveDate> = NaiveDate::from_ymd_opt(year, 1, 1)
+                .into_iter()
+                .flat_map(|d| d.iter_days())
+                .take_while(|d| d.year() == year)
+                .collect();
+            return nth_weekdays(&days, &self.by_day);
+        } else {
+            NaiveDate::from_ymd_opt(year, start.month(), 1)
+                .into_iter()
+                .collect()
+        };
+
+        months
+            .into_iter()
+            .flat_map(|first| self.month_candidates(first, start))
+            .collect()
+    }
+
+    /// BYxxx parts which only limit the candidates of the period
+    fn matches_limits(&self, date: NaiveDate) -> bool {
+        if !self.by_month.is_empty() && !self.by_month.contains(&date.month()) {
+            return false;
+        }
+
+        let expanded_month_days = matches!(self.frequency, Frequency::Monthly | Frequency::Yearly);
+        if !self.by_month_day.is_empty()
+            && !expanded_month_days
+            && resolve_month_days(&month_days(date.with_day(1).unwrap()), &self.by_month_day)
+                .binary_search(&date)
+                .is_err()
+        {
+            return false;
+        }
+
+        let expanded_days = match self.frequency {
+            Frequency::Weekly => true,
+            Frequency::Monthly | Frequency::Yearly => self.by_month_day.is_empty(),
+            Frequency::Daily => false,
+        };
+        if !self.by_day.is_empty()
+            && !expanded_days
+            && !self.by_day.iter().any(|b| b.weekday == date.weekday())
+        {
+            return false;
+        }
+
+        true
+    }
+}
+
+impl FromStr for RRule {
+    type Err = Error;
+
+    /// Parse `FREQ=...;...`, with or without the `RRULE:` prefix
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let s = s.strip_prefix("RRULE:").unwrap_or(s);
+
+        let mut frequency = None;
+        let mut rule = RRule::new(Frequency::Daily);
+
+        for part in s.split(';').filter(|p| !p.is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| invalid(format!("`{}` is not KEY=VALUE", part)))?;
+
+            match key.to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    frequency = Some(match value.to_ascii_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        _ => return Err(invalid(format!("unsupported FREQ `{}`", value))),
+                    })
+                }
+                "INTERVAL" => {
+                    rule.interval = parse_number(key, value)?;
+                    if rule.interval == 0 {
+                        return Err(invalid("INTERVAL must be positive"));
+                    }
+                }
+                "COUNT" => rule.count = Some(parse_number(key, value)?),
+                "UNTIL" => rule.until = Some(parse_date(value)?),
+                "WKST" => rule.week_start = parse_weekday(value)?,
+                "BYDAY" => {
+                    rule.by_day = value
+                        .split(',')
+                        .map(parse_by_day)
+                        .collect::<Result<_, _>>()?
+                }
+                "BYMONTHDAY" => {
+                    rule.by_month_day = value
+                        .split(',')
+                        .map(|v| {
+                            let day: i32 = parse_number(key, v)?;
+                            if day == 0 || !(-31..=31).contains(&day) {
+                                return Err(invalid(format!("BYMONTHDAY `{}` is out of range", v)));
+                            }
+                            Ok(day)
+                        })
+                        .collect::<Result<_, _>>()?
+                }
+                "BYMONTH" => {
+                    rule.by_month = value
+                        .split(',')
+                        .map(|v| {
+                            let month: u32 = parse_number(key, v)?;
+                            if !(1..=12).contains(&month) {
+                                return Err(invalid(format!("BYMONTH `{}` is out of range", v)));
+                            }
+                            Ok(month)
+                        })
+                        .collect::<Result<_, _>>()?
+                }
+                _ => return Err(invalid(format!("unsupported part `{}`", key))),
+            }
+        }
+
+        rule.frequency = frequency.ok_or_else(|| invalid("FREQ is missing"))?;
+
+        // e.g. BYMONTH=2;BYMONTHDAY=30
+        const LONGEST: [u32; 12] = [31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+        let longest = |month: &u32| LONGEST[*month as usize - 1];
+        if !rule.by_month.is_empty()
+            && !rule.by_month_day.is_empty()
+            && !rule.by_month.iter().any(|month| {
+                rule.by_month_day
+                    .iter()
+                    .any(|day| day.unsigned_abs() <= longest(month))
+            })
+        {
+            return Err(invalid(format!("`{}` never matches", s)));
+        }
+
+        Ok(rule)
+    }
+}
+
+impl Display for RRule {
+    /// `FREQ=...;...` without the `RRULE:` prefix
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let frequency = match self.frequency {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+            Frequency::Yearly => "YEARLY",
+        };
+        write!(f, "FREQ={}", frequency)?;
+
+        if self.interval != 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        if !self.by_day.is_empty() {
+            let days: Vec<String> = self
+                .by_day
+                .iter()
+                .map(|b| match b.n {
+                    Some(n) => format!("{}{}", n, weekday_code(b.weekday)),
+                    None => weekday_code(b.weekday).to_string(),
+                })
+                .collect();
+            write!(f, ";BYDAY={}", days.join(","))?;
+        }
+        if !self.by_month_day.is_empty() {
+            write!(f, ";BYMONTHDAY={}", join(&self.by_month_day))?;
+        }
+        if !self.by_month.is_empty() {
+            write!(f, ";BYMONTH={}", join(&self.by_month))?;
+        }
+        if let Some(count) = self.count {
+            write!(f, ";COUNT={}", count)?;
+        }
+        if let Some(until) = self.until {
+            write!(f, ";UNTIL={}", until.format("%Y%m%d"))?;
+        }
+        if self.week_start != Weekday::Mon {
+            write!(f, ";WKST={}", weekday_code(self.week_start))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Recurring dates: a start date, a rule and excluded dates
+///
+/// Can be parsed from iCalendar-style lines:
+/// ```text
+/// DTSTART:20240102
+/// RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=TU
+/// EXDATE:20240116,20240213
+/// ```
+/// A `DTSTART` or `EXDATE` with a time or a `TZID` is an error; timed events are read by
+/// [`ICalendar`](crate::ICalendar).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recurrence {
+    start: NaiveDate,
+    rule: RRule,
+    exdates: BTreeSet<NaiveDate>,
+}
+
+impl Recurrence {
+    pub fn new(start: NaiveDate, rule: RRule) -> Self {
+        Self {
+            start,
+            rule,
+            exdates: BTreeSet::new(),
+        }
+    }
+
+    /// Exclude a date (EXDATE)
+    pub fn exclude(&mut self, date: NaiveDate) {
+        self.exdates.insert(date);
+    }
+
+    pub fn start(&self) -> NaiveDate {
+        self.start
+    }
+
+    pub fn rule(&self) -> &RRule {
+        &self.rule
+    }
+
+    pub fn exdates(&self) -> impl Iterator<Item = NaiveDate> + '_ {
+        self.exdates.iter().copied()
+    }
+
+    /// Occurrences in the range
+    pub fn between(&self, range: RangeInclusive<NaiveDate>) -> Vec<NaiveDate> {
+        self.rule
+            .dates(self.start, *range.end())
+            .into_iter()
+            .filter(|d| range.contains(d) && !self.exdates.contains(d))
+            .collect()
+    }
+
+    /// Mark the occurrences in the displayed range of the calendar
+    pub fn mark<C: Calendar + ?Sized>(&self, calendar: &mut C) {
+        for date in self.displayed(calendar) {
+            calendar.mark(date);
+        }
+    }
+
+    /// Mark the occurrences in the displayed range of the calendar with a category
+    pub fn mark_category<C: Calendar + ?Sized>(&self, calendar: &mut C, category: &Category) {
+        for date in self.displayed(calendar) {
+            calendar.mark_category(date, category);
+        }
+    }
+
+    fn displayed<C: Calendar + ?Sized>(&self, calendar: &C) -> Vec<NaiveDate> {
+        calendar
+            .date_range()
+            .map(|range| self.between(range))
+            .unwrap_or_default()
+    }
+}
+
+impl FromStr for Recurrence {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut start = None;
+        let mut rule = None;
+        let mut exdates = BTreeSet::new();
+
+        for line in s.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            let (name, value) = line
+                .split_once(':')
+                .ok_or_else(|| invalid(format!("`{}` is not NAME:VALUE", line)))?;
+            // drop parameters such as `;VALUE=DATE`
+            let name = name.split(';').next().unwrap_or_default();
+
+            // dates only: times and zones are for `ICalendar`
+            let name_upper = name.to_ascii_uppercase();
+            if matches!(name_upper.as_str(), "DTSTART" | "EXDATE")
+                && (value.contains('T') || line.to_ascii_uppercase().contains(";TZID="))
+            {
+                return Err(invalid(format!(
+                    "`{}` has a time, which a `Recurrence` of dates cannot keep",
+                    line
+                )));
+            }
+
+            match name_upper.as_str() {
+                "DTSTART" => start = Some(parse_date(value)?),
+                "RRULE" => rule = Some(value.parse()?),
+                "EXDATE" => {
+                    for date in value.split(',') {
+                        exdates.insert(parse_date(date)?);
+                    }
+                }
+                _ => return Err(invalid(format!("unsupported property `{}`", name))),
+            }
+        }
+
+        Ok(Self {
+            start: start.ok_or_else(|| invalid("DTSTART is missing"))?,
+            rule: rule.ok_or_else(|| invalid("RRULE is missing"))?,
+            exdates,
+        })
+    }
+}
+
+fn invalid(message: impl Into<String>) -> Error {
+    Error::InvalidRecurrence(message.into())
+}
+
+fn parse_number<T: FromStr>(key: &str, value: &str) -> Result<T, Error> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| invalid(format!("{} `{}` is not a number", key, value)))
+}
+
+/// `YYYYMMDD`, optionally followed by a time (`T090000Z`) which is ignored
+pub(crate) fn parse_date(value: &str) -> Result<NaiveDate, Error> {
+    let value = value.trim();
+    let date = value.split('T').next().unwrap_or_default();
+
+    NaiveDate::parse_from_str(date, "%Y%m%d")
+        .map_err(|_| invalid(format!("`{}` is not a date", value)))
+}
+
+fn weekday_code(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+fn join<T: Display>(values: &[T]) -> String {
+    values
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn parse_weekday(value: &str) -> Result<Weekday, Error> {
+    match value.trim().to_ascii_uppercase().as_str() {
+        "MO" => Ok(Weekday::Mon),
+        "TU" => Ok(Weekday::Tue),
+        "WE" => Ok(Weekday::Wed),
+        "TH" => Ok(Weekday::Thu),
+        "FR" => Ok(Weekday::Fri),
+        "SA" => Ok(Weekday::Sat),
+        "SU" => Ok(Weekday::Sun),
+        _ => Err(invalid(format!("`{}` is not a weekday", value))),
+    }
+}
+
+fn parse_by_day(value: &str) -> Result<ByDay, Error> {
+    let value = value.trim();
+    if value.len() < 2 || !value.is_char_boundary(value.len() - 2) {
+        return Err(invalid(format!("`{}` is not a weekday", value)));
+    }
+
+    let (n, weekday) = value.split_at(value.len() - 2);
+    let n = if n.is_empty() {
+        None
+    } else {
+        let n: i32 = parse_number("BYDAY", n.strip_prefix('+').unwrap_or(n))?;
+        if n == 0 || !(-53..=53).contains(&n) {
+            return Err(invalid(format!("BYDAY `{}` is out of range", value)));
+        }
+        Some(n)
+    };
+
+    Ok(ByDay {
+        n,
+        weekday: parse_weekday(weekday)?,
+    })
+}
+
+pub(crate) fn month_days(first: NaiveDate) -> Vec<NaiveDate> {
+    first
+        .iter_days()
+        .take_while(|d| d.month() == first.month())
+        .collect()
+}
+
+/// Days of the month for BYMONTHDAY values (negative values count from the end)
+fn resolve_month_days(days: &[NaiveDate], by_month_day: &[i32]) -> Vec<NaiveDate> {
+    let mut dates: Vec<NaiveDate> = by_month_day
+        .iter()
+        .filter_map(|n| {
+            let index = if *n > 0 {
+                *n as usize - 1
+            } else {
+                days.len().checked_sub(n.unsigned_abs() as usize)?
+            };
+            days.get(index).copied()
+        })
+        .collect();
+    dates.sort();
+
+    dates
+}
+
+/// Days matching the BYDAY entries within `days` (a month or a year)
+pub(crate) fn nth_weekdays(days: &[NaiveDate], by_day: &[ByDay]) -> Vec<NaiveDate> {
+    by_day
+        .iter()
+        .flat_map(|b| {
+            let matching: Vec<NaiveDate> = days
+                .iter()
+                .filter(|d| d.weekday() == b.weekday)
+                .copied()
+                .collect();
+
+            match b.n {
+                None => matching,
+                Some(n) if n > 0 => matching.get(n as usize - 1).copied().into_iter().collect(),
+                Some(n) => matching
+                    .len()
+                    .checked_sub(n.unsigned_abs() as usize)
+                    .and_then(|i| matching.get(i).copied())
+                    .into_iter()
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{n_date, MonthCalendar};
+
+    use super::*;
+
+    fn between(rule: &str, start: NaiveDate, end: NaiveDate) -> Vec<NaiveDate> {
+        Recurrence::new(start, rule.parse().unwrap()).between(start..=end)
+    }
+
+    #[test]
+    fn test_rules() {
+        assert_eq!(
+            between(
+                "FREQ=WEEKLY;INTERVAL=2;BYDAY=TU",
+                n_date!(2024, 1, 2),
+                n_date!(2024, 2, 29)
+            ),
+            [
+                n_date!(2024, 1, 2),
+                n_date!(2024, 1, 16),
+                n_date!(2024, 1, 30),
+                n_date!(2024, 2, 13),
+                n_date!(2024, 2, 27)
+            ]
+        );
+
+        assert_eq!(
+            between(
+                "FREQ=MONTHLY;BYDAY=-1FR",
+                n_date!(2024, 1, 1),
+                n_date!(2024, 3, 31)
+            ),
+            [
+                n_date!(2024, 1, 1),
+                n_date!(2024, 1, 26),
+                n_date!(2024, 2, 23),
+                n_date!(2024, 3, 29)
+            ]
+        );
+
+        assert_eq!(
+            between(
+                "FREQ=MONTHLY;BYMONTHDAY=15,-1;COUNT=5",
+                n_date!(2024, 1, 20),
+                n_date!(2024, 12, 31)
+            ),
+            [
+                n_date!(2024, 1, 20),
+                n_date!(2024, 1, 31),
+                n_date!(2024, 2, 15),
+                n_date!(2024, 2, 29),
+                n_date!(2024, 3, 15)
+            ]
+        );
+
+        assert_eq!(
+            between(
+                "FREQ=YEARLY;BYMONTH=11;BYDAY=4TH;UNTIL=20260101",
+                n_date!(2024, 1, 1),
+                n_date!(2030, 12, 31)
+            ),
+            [
+                n_date!(2024, 1, 1),
+                n_date!(2024, 11, 28),
+                n_date!(2025, 11, 27)
+            ]
+        );
+
+        assert_eq!(
+            between(
+                "FREQ=DAILY;INTERVAL=3;BYDAY=MO,TU,WE,TH,FR",
+                n_date!(2024, 6, 1),
+                n_date!(2024, 6, 12)
+            ),
+            [
+                n_date!(2024, 6, 1),
+                n_date!(2024, 6, 4),
+                n_date!(2024, 6, 7),
+                n_date!(2024, 6, 10)
+            ]
+        );
+
+        // DTSTART is the first of the COUNT occurrences even when it does not match
+        assert_eq!(
+            between(
+                "FREQ=MONTHLY;BYMONTHDAY=15;COUNT=3",
+                n_date!(2024, 1, 1),
+                n_date!(2024, 12, 31)
+            ),
+            [
+                n_date!(2024, 1, 1),
+                n_date!(2024, 1, 15),
+                n_date!(2024, 2, 15)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        let recurrence: Recurrence = "DTSTART;VALUE=DATE:20240102\n\
+                                      RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=TU\n\
+                                      EXDATE:20240116,20240213"
+            .parse()
+            .unwrap();
+
+        let mut cal = MonthCalendar::from_ym(2024, 1).unwrap();
+        recurrence.mark(&mut cal);
+
+        assert!(cal.is_marked(n_date!(2024, 1, 2)));
+        assert!(!cal.is_marked(n_date!(2024, 1, 16)));
+        assert!(cal.is_marked(n_date!(2024, 1, 30)));
+
+        assert!("FREQ=HOURLY".parse::<RRule>().is_err());
+        assert!("INTERVAL=2".parse::<RRule>().is_err());
+        assert!("FREQ=MONTHLY;BYDAY=0MO".parse::<RRule>().is_err());
+        assert!("FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=30"
+            .parse::<RRule>()
+            .is_err());
+        assert!("FREQ=YEARLY;BYMONTH=2,3;BYMONTHDAY=30"
+            .parse::<RRule>()
+            .is_ok());
+        assert!("DTSTART:20240102T090000\nRRULE:FREQ=DAILY"
+            .parse::<Recurrence>()
+            .is_err());
+
+        // rules built by hand that never match stop after one 400-year cycle
+        let mut rule = RRule::new(Frequency::Yearly);
+        rule.by_month = vec![4];
+        rule.by_month_day = vec![31];
+        assert_eq!(
+            rule.dates(n_date!(2024, 1, 1), NaiveDate::MAX),
+            [n_date!(2024, 1, 1)]
+        );
+    }
+}
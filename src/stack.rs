@@ -0,0 +1,182 @@
+use std::{fmt::Display, marker::PhantomData};
+
+use crate::{forward::forward_calendar_to_all, Alignment, Calendar, Region, VerticalAlignment};
+
+/// Direction of a [`Stack`]
+pub trait Direction {
+    const HORIZONTAL: bool;
+}
+
+/// Calendars side by side, see [`HStack`]
+#[derive(Debug)]
+pub struct Horizontal;
+
+/// Calendars one under another, see [`VStack`]
+#[derive(Debug)]
+pub struct Vertical;
+
+impl Direction for Horizontal {
+    const HORIZONTAL: bool = true;
+}
+
+impl Direction for Vertical {
+    const HORIZONTAL: bool = false;
+}
+
+/// Calendars side by side, as tall as the tallest one
+pub type HStack = Stack<Horizontal>;
+
+/// Calendars one under another, as wide as the widest one
+pub type VStack = Stack<Vertical>;
+
+/// Calendars laid out in one direction, without a title
+///
+/// Stacks can be nested to build layouts that do not fit a grid:
+/// ```
+/// use chrono::Weekday;
+/// use text_calendar::{BasicMarker, Calendar, HStack, Legend, MonthCalendar, VStack, YearCalendar};
+///
+/// let year = YearCalendar::new(2024, Weekday::Sun, 3, BasicMarker::SquareBrackets);
+/// let june = MonthCalendar::new(2024, 6, Weekday::Sun, 5, BasicMarker::SquareBrackets).unwrap();
+/// let legend = Legend::from_calendar(&year);
+///
+/// let top = HStack::new(vec![Box::new(year), Box::new(june)]);
+/// let mut layout = VStack::new(vec![Box::new(top), Box::new(legend)]);
+/// layout.mark(chrono::NaiveDate::from_ymd_opt(2024, 6, 3).unwrap());
+/// println!("{}", layout);
+/// ```
+/// Marks and the other settings are passed to every calendar, like [`Calendars`](crate::Calendars).
+#[derive(Debug)]
+pub struct Stack<D: Direction> {
+    calendars: Vec<Box<dyn Calendar>>,
+    gap: usize,
+    alignment: Alignment,
+    vertical_alignment: VerticalAlignment,
+    direction: PhantomData<D>,
+}
+
+impl<D: Direction> Stack<D> {
+    /// Gap of 2 columns between calendars side by side, 1 line between calendars one under another
+    pub fn new(calendars: Vec<Box<dyn Calendar>>) -> Self {
+        Self {
+            calendars,
+            gap: if D::HORIZONTAL { 2 } else { 1 },
+            alignment: Alignment::Left,
+            vertical_alignment: VerticalAlignment::Top,
+            direction: PhantomData,
+        }
+    }
+
+    /// Columns (`HStack`) or lines (`VStack`) between calendars
+    pub fn set_gap(&mut self, gap: usize) {
+        self.gap = gap;
+    }
+
+    pub fn push(&mut self, calendar: Box<dyn Calendar>) {
+        self.calendars.push(calendar);
+    }
+
+    fn gaps(&self) -> usize {
+        self.gap * self.calendars.len().saturating_sub(1)
+    }
+}
+
+impl HStack {
+    /// Placement of calendars shorter than the stack (default: top)
+    pub fn set_vertical_alignment(&mut self, alignment: VerticalAlignment) {
+        self.vertical_alignment = alignment;
+    }
+}
+
+impl VStack {
+    /// Placement of calendars narrower than the stack (default: left)
+    pub fn set_alignment(&mut self, alignment: Alignment) {
+        self.alignment = alignment;
+    }
+}
+
+impl<D: Direction> Calendar for Stack<D> {
+    fn width(&self) -> usize {
+        let widths = self.calendars.iter().map(|c| c.width());
+
+        if D::HORIZONTAL {
+            widths.sum::<usize>() + self.gaps()
+        } else {
+            widths.max().unwrap_or_default()
+        }
+    }
+
+    fn height(&self) -> usize {
+        let heights = self.calendars.iter().map(|c| c.height());
+
+        if D::HORIZONTAL {
+            heights.max().unwrap_or_default()
+        } else {
+            heights.sum::<usize>() + self.gaps()
+        }
+    }
+
+    fn draw(&self, region: &mut Region<'_>) {
+        let (width, height) = (self.width(), self.height());
+        let mut offset = 0;
+
+        for calendar in &self.calendars {
+            if D::HORIZONTAL {
+                let y = self.vertical_alignment.offset(calendar.height(), height);
+                calendar.draw(&mut region.region(offset, y).with_width(calendar.width()));
+                offset += calendar.width() + self.gap;
+            } else {
+                let x = self.alignment.offset(calendar.width(), width);
+                calendar.draw(&mut region.region(x, offset).with_width(calendar.width()));
+                offset += calendar.height() + self.gap;
+            }
+        }
+    }
+
+    forward_calendar_to_all!(calendars);
+}
+
+impl<D: Direction> Display for Stack<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.render(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Weekday;
+
+    use crate::{n_date, utils::display_width, BasicMarker, MonthCalendar};
+
+    use super::*;
+
+    #[test]
+    fn test() {
+        // June 2024 has 6 weeks, February 2026 has 4
+        let june = || Box::new(MonthCalendar::from_ym(2024, 6).unwrap());
+        let feb =
+            || Box::new(MonthCalendar::new(2026, 2, Weekday::Sun, 3, BasicMarker::None).unwrap());
+
+        let mut row = HStack::new(vec![june(), feb()]);
+        row.set_vertical_alignment(VerticalAlignment::Bottom);
+        assert_eq!((row.width(), row.height()), (28 + 2 + 21, 8));
+
+        let mut column = VStack::new(vec![Box::new(row), feb()]);
+        column.set_alignment(Alignment::Right);
+        assert_eq!((column.width(), column.height()), (51, 8 + 1 + 6));
+
+        let output = column.to_string();
+        let lines: Vec<&str> = output.split('\n').collect();
+        assert_eq!(lines.len(), column.height());
+        assert!(lines.iter().all(|l| display_width(l) == column.width()));
+        assert_eq!(lines[0].trim(), "June");
+        assert_eq!(lines[2][30..].trim(), "February");
+        assert!(lines[4].ends_with(" 1  2  3  4  5  6  7 "));
+        assert_eq!(lines[9].find("February"), Some(30 + 6));
+        assert!(lines[11].ends_with(" 1  2  3  4  5  6  7 "));
+
+        column.mark(n_date!(2026, 2, 3));
+        assert!(column.is_marked(n_date!(2026, 2, 3)));
+        assert!(!column.is_marked(n_date!(2026, 2, 4)));
+    }
+}
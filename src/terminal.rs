@@ -0,0 +1,13 @@
+/// Width of the terminal from the `COLUMNS` environment variable
+///
+/// Shells set `COLUMNS` but do not always export it; programs that need the width of the
+/// tty itself should query it (e.g. with `ioctl`) and pass it to
+/// [`Calendars::fit`](crate::Calendars::fit) or [`YearCalendar::fit`](crate::YearCalendar::fit).
+pub fn terminal_width() -> Option<usize> {
+    std::env::var("COLUMNS")
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+        .filter(|width| *width > 0)
+}
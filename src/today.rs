@@ -0,0 +1,70 @@
+use std::{fmt::Debug, rc::Rc};
+
+use chrono::{Local, NaiveDate};
+
+use crate::Marker;
+
+/// Source of the current date
+pub trait Clock {
+    fn today(&self) -> NaiveDate;
+}
+
+/// Local date of the system clock
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LocalClock;
+
+impl Clock for LocalClock {
+    fn today(&self) -> NaiveDate {
+        Local::now().date_naive()
+    }
+}
+
+/// Always the same date (e.g. for tests)
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock(pub NaiveDate);
+
+impl Clock for FixedClock {
+    fn today(&self) -> NaiveDate {
+        self.0
+    }
+}
+
+impl Debug for dyn Clock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Clock({})", self.today())
+    }
+}
+
+/// Highlight of the current date, drawn with its own marker
+///
+/// The date is read from the clock every time the calendar is displayed,
+/// and is not part of the marked dates. The highlight replaces the other markers
+/// of the day.
+#[derive(Debug, Clone)]
+pub struct TodayHighlight {
+    clock: Rc<dyn Clock>,
+    marker: Rc<dyn Marker>,
+}
+
+impl TodayHighlight {
+    /// Highlight of the local date
+    pub fn new(marker: impl Marker + 'static) -> Self {
+        Self {
+            clock: Rc::new(LocalClock),
+            marker: Rc::new(marker),
+        }
+    }
+
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Rc::new(clock);
+        self
+    }
+
+    pub fn today(&self) -> NaiveDate {
+        self.clock.today()
+    }
+
+    pub fn marker(&self) -> &dyn Marker {
+        self.marker.as_ref()
+    }
+}
@@ -1,6 +1,111 @@
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
 #[macro_export]
 macro_rules! n_date {
     ($year: expr, $month: expr, $day: expr) => {
         chrono::NaiveDate::from_ymd_opt($year, $month, $day).expect("Invalid date")
     };
 }
+
+/// Characters of `s` with their byte index and width, skipping ANSI escape sequences
+fn columns(s: &str) -> impl Iterator<Item = (usize, usize)> + '_ {
+    let mut in_escape = false;
+
+    s.char_indices().filter_map(move |(i, c)| {
+        if in_escape {
+            in_escape = !c.is_ascii_alphabetic();
+            return None;
+        }
+        if c == '\x1b' {
+            in_escape = true;
+            return None;
+        }
+
+        Some((i, c.width().unwrap_or(0)))
+    })
+}
+
+/// Width of `s` in terminal columns (CJK characters count as 2, escape codes as 0)
+pub(crate) fn display_width(s: &str) -> usize {
+    if s.bytes().all(|b| (b' '..=b'~').contains(&b)) {
+        return s.len();
+    }
+    if !s.contains('\x1b') {
+        return s.width();
+    }
+
+    columns(s).map(|(_, width)| width).sum()
+}
+
+/// Cut `s` so that it fits in `width` columns
+pub(crate) fn truncate(s: &str, width: usize) -> &str {
+    let mut used = 0;
+    for (i, w) in columns(s) {
+        used += w;
+        if used > width {
+            return &s[..i];
+        }
+    }
+
+    s
+}
+
+/// Same as `format!("{: ^width$}", s)`, but counts columns instead of chars
+pub(crate) fn center(s: &str, width: usize) -> String {
+    let s = truncate(s, width);
+    let space = width - display_width(s);
+
+    format!(
+        "{}{}{}",
+        " ".repeat(space / 2),
+        s,
+        " ".repeat(space - space / 2)
+    )
+}
+
+/// Fill `s` with spaces on the right up to `width` columns
+pub(crate) fn pad(s: &str, width: usize) -> String {
+    format!(
+        "{}{}",
+        s,
+        " ".repeat(width.saturating_sub(display_width(s)))
+    )
+}
+
+/// Split `text` into lines of at most `width` columns, breaking at spaces where possible
+pub(crate) fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = vec![];
+    let mut line = String::new();
+
+    for word in text.split_whitespace() {
+        let mut word = word;
+
+        if !line.is_empty() && display_width(&line) + 1 + display_width(word) <= width {
+            line.push(' ');
+            line.push_str(word);
+            continue;
+        }
+
+        if !line.is_empty() {
+            lines.push(std::mem::take(&mut line));
+        }
+
+        while display_width(word) > width {
+            let head = truncate(word, width.max(1));
+            let head = if head.is_empty() {
+                &word[..word.chars().next().unwrap().len_utf8()]
+            } else {
+                head
+            };
+            lines.push(head.to_string());
+            word = &word[head.len()..];
+        }
+        line.push_str(word);
+    }
+
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+
+    lines
+}
@@ -0,0 +1,160 @@
+use std::{ops::RangeInclusive, rc::Rc};
+
+use chrono::{Datelike, NaiveDate, Weekday};
+
+use crate::{Calendar, Category, Holiday, Marker};
+
+/// Working days of the week
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Workweek {
+    weekend: Vec<Weekday>,
+}
+
+impl Workweek {
+    /// Workweek with the given days off
+    pub fn new(weekend: &[Weekday]) -> Self {
+        Self {
+            weekend: weekend.to_vec(),
+        }
+    }
+
+    /// Monday to Friday
+    pub fn sat_sun() -> Self {
+        Self::new(&[Weekday::Sat, Weekday::Sun])
+    }
+
+    /// Sunday to Thursday
+    pub fn fri_sat() -> Self {
+        Self::new(&[Weekday::Fri, Weekday::Sat])
+    }
+
+    /// Monday to Saturday
+    pub fn sun_only() -> Self {
+        Self::new(&[Weekday::Sun])
+    }
+
+    pub fn is_weekend(&self, weekday: Weekday) -> bool {
+        self.weekend.contains(&weekday)
+    }
+
+    /// Whether the date is neither a weekend nor one of `holidays`
+    pub fn is_working_day(&self, date: NaiveDate, holidays: &[Holiday]) -> bool {
+        !self.is_weekend(date.weekday()) && !holidays.iter().any(|h| h.date == date)
+    }
+
+    /// Number of working days in the range
+    pub fn working_days_between(
+        &self,
+        range: RangeInclusive<NaiveDate>,
+        holidays: &[Holiday],
+    ) -> usize {
+        range
+            .start()
+            .iter_days()
+            .take_while(|d| d <= range.end())
+            .filter(|d| self.is_working_day(*d, holidays))
+            .count()
+    }
+
+    /// Number of working days shown in the calendar, excluding its holidays (marked by
+    /// `mark_holiday` or with a category named [`Category::HOLIDAY`])
+    pub fn working_days<C: Calendar + ?Sized>(&self, calendar: &C) -> usize {
+        let Some(range) = calendar.date_range() else {
+            return 0;
+        };
+
+        range
+            .start()
+            .iter_days()
+            .take_while(|d| d <= range.end())
+            .filter(|d| {
+                !self.is_weekend(d.weekday())
+                    && !calendar
+                        .categories_of(*d)
+                        .iter()
+                        .any(|c| c.name() == Category::HOLIDAY)
+            })
+            .count()
+    }
+}
+
+impl Default for Workweek {
+    fn default() -> Self {
+        Self::sat_sun()
+    }
+}
+
+/// Style of the weekend columns (days and weekday labels)
+///
+/// The marker is only used for days without a category, so it suits dimming or
+/// coloring with ANSI escape codes (which do not count toward the width of the cells).
+#[derive(Debug, Clone)]
+pub struct WeekendStyle {
+    workweek: Workweek,
+    marker: Rc<dyn Marker>,
+}
+
+impl WeekendStyle {
+    pub fn new(workweek: Workweek, marker: impl Marker + 'static) -> Self {
+        Self {
+            workweek,
+            marker: Rc::new(marker),
+        }
+    }
+
+    pub fn workweek(&self) -> &Workweek {
+        &self.workweek
+    }
+
+    pub fn marker(&self) -> &dyn Marker {
+        self.marker.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{n_date, utils::display_width, BasicMarker, MonthCalendar};
+
+    use super::*;
+
+    struct Dim;
+
+    impl Marker for Dim {
+        fn decorate(&self, day: &str) -> String {
+            format!("\x1b[2m{}\x1b[0m", day)
+        }
+    }
+
+    #[test]
+    fn test() {
+        let mut cal = MonthCalendar::from_ym(2024, 6).unwrap();
+        cal.mark_holiday(&Holiday::new(n_date!(2024, 6, 19), "Juneteenth"));
+        cal.set_weekend_style(Some(WeekendStyle::new(
+            Workweek::fri_sat(),
+            BasicMarker::Char('.'),
+        )));
+
+        let output = cal.to_string();
+        let lines: Vec<&str> = output.split('\n').collect();
+        assert_eq!(lines[1], " Su  Mo  Tu  We  Th .Fr..Sa.");
+        assert_eq!(lines[3], " 2   3   4   5   6  .7 ..8 .");
+
+        assert_eq!(Workweek::sat_sun().working_days(&cal), 19);
+        assert_eq!(Workweek::fri_sat().working_days(&cal), 20);
+        assert_eq!(Workweek::sun_only().working_days(&cal), 24);
+
+        cal.mark_category(
+            n_date!(2024, 6, 20),
+            &Category::new(Category::HOLIDAY, BasicMarker::Char('*')),
+        );
+        assert_eq!(Workweek::sat_sun().working_days(&cal), 18);
+
+        // dimmed weekends keep the columns aligned
+        cal.set_weekend_style(Some(WeekendStyle::new(Workweek::default(), Dim)));
+        let output = cal.to_string();
+        assert!(output.contains("\x1b[2mSa\x1b[0m"));
+        assert!(output
+            .split('\n')
+            .all(|line| display_width(line) == cal.width()));
+    }
+}
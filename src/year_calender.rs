@@ -1,9 +1,13 @@
-use std::fmt::Display;
+use std::fmt::{Display, Write};
 
 use chrono::Weekday;
 
-use crate::{Calendar, CalendarCollection, Calendars, Marker, MonthCalendar};
+use crate::{
+    forward::forward_calendar, Calendar, CalendarCollection, Calendars, Error, Marker,
+    MonthCalendar, MonthCalendarBuilder, Region,
+};
 
+#[derive(Debug)]
 pub struct YearCalendar {
     year: i32,
     calendars: Calendars,
@@ -33,6 +37,43 @@ impl YearCalendar {
         Self { year, calendars }
     }
 
+    /// Year of months built from `template` (its year and month are ignored)
+    pub fn from_builder(year: i32, template: &MonthCalendarBuilder) -> Result<Self, Error> {
+        let mut calendar_list: Vec<Box<dyn Calendar>> = vec![];
+
+        for month in 1..13 {
+            calendar_list.push(Box::new(template.build_month(year, month)?));
+        }
+
+        let calendars = Calendars::new(calendar_list, year.to_string(), 3);
+
+        Ok(Self { year, calendars })
+    }
+
+    /// Year of months built from `template`, fitting in `width` columns
+    ///
+    /// The number of months per row is the largest of 12, 6, 4, 3, 2 and 1 that fits. If a
+    /// single month is too wide, narrower day widths are tried down to the narrowest one the
+    /// template allows.
+    pub fn fit(year: i32, template: &MonthCalendarBuilder, width: usize) -> Result<Self, Error> {
+        let mut day_width = template.configured_day_width();
+        let mut calendar = Self::from_builder(year, template)?;
+
+        loop {
+            match calendar.calendars.fit_cols(&[12, 6, 4, 3, 2, 1], width) {
+                Ok(()) => return Ok(calendar),
+                Err(error) if day_width == 0 => return Err(error),
+                Err(error) => {
+                    day_width -= 1;
+                    match Self::from_builder(year, &template.clone().day_width(day_width)) {
+                        Ok(narrower) => calendar = narrower,
+                        Err(_) => return Err(error),
+                    }
+                }
+            }
+        }
+    }
+
     pub fn year(&self) -> i32 {
         self.year
     }
@@ -40,28 +81,70 @@ impl YearCalendar {
 
 impl Display for YearCalendar {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.calendars)
+        self.render(f)
     }
 }
 
 impl Calendar for YearCalendar {
-    fn is_marked(&self, date: chrono::prelude::NaiveDate) -> bool {
-        self.calendars.is_marked(date)
+    fn width(&self) -> usize {
+        self.calendars.width()
     }
 
-    fn mark(&mut self, date: chrono::prelude::NaiveDate) {
-        self.calendars.mark(date)
+    fn height(&self) -> usize {
+        self.calendars.height()
     }
 
-    fn unmark(&mut self, date: chrono::prelude::NaiveDate) {
-        self.calendars.unmark(date)
+    fn draw(&self, region: &mut Region<'_>) {
+        self.calendars.draw(region)
     }
 
-    fn height(&self) -> usize {
-        self.calendars.height()
+    fn render(&self, w: &mut dyn Write) -> std::fmt::Result {
+        self.calendars.render(w)
     }
 
-    fn width(&self) -> usize {
-        self.calendars.width()
+    forward_calendar!(calendars);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BasicMarker;
+
+    use super::*;
+
+    #[test]
+    fn test_fit() {
+        // months of 28 columns: 3 per row take 92 columns, 2 per row 60
+        let template = MonthCalendar::builder();
+        assert_eq!(YearCalendar::fit(2024, &template, 100).unwrap().width(), 92);
+        assert_eq!(YearCalendar::fit(2024, &template, 80).unwrap().width(), 60);
+        assert_eq!(
+            YearCalendar::fit(2024, &template, 25).unwrap_err(),
+            Error::TooNarrow {
+                available: 25,
+                required: 28
+            }
+        );
+
+        // without brackets, days fit in 3 and then 2 columns
+        let template = MonthCalendar::builder().marker(BasicMarker::None);
+        assert_eq!(YearCalendar::fit(2024, &template, 25).unwrap().width(), 21);
+        assert_eq!(
+            YearCalendar::fit(2024, &template, 10)
+                .unwrap_err()
+                .to_string(),
+            "too narrow: 14 columns are required, but only 10 are available"
+        );
+
+        let months: Vec<Box<dyn Calendar>> = (1..=5)
+            .map(|month| {
+                Box::new(MonthCalendar::from_ym(2024, month).unwrap()) as Box<dyn Calendar>
+            })
+            .collect();
+        let calendars = Calendars::fit(months, "".to_string(), 70).unwrap();
+        assert_eq!(calendars.width(), 60);
+        assert_eq!(
+            calendars.to_string().split('\n').count(),
+            calendars.height()
+        );
     }
 }
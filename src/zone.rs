@@ -0,0 +1,533 @@
+use std::rc::Rc;
+
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc,
+};
+
+use crate::{
+    recurrence::{month_days, nth_weekdays},
+    ByDay,
+};
+
+/// Time zone of iCalendar date-times
+///
+/// Fixed offsets are always available. IANA time zones (`Asia/Tokyo`, with daylight
+/// saving time) need the `tz` feature; without it, the `VTIMEZONE` of a file is read as
+/// [`ZoneRules`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Zone {
+    Fixed(FixedOffset),
+    /// Yearly rules of a `VTIMEZONE`
+    Rules(Rc<ZoneRules>),
+    #[cfg(feature = "tz")]
+    Tz(chrono_tz::Tz),
+}
+
+impl Zone {
+    pub fn utc() -> Self {
+        Zone::Fixed(FixedOffset::east_opt(0).unwrap())
+    }
+
+    /// Zone of a `TZID` parameter: `UTC`, a fixed offset such as `UTC+0900`, or, with the
+    /// `tz` feature, an IANA name or a Windows name such as `Tokyo Standard Time`
+    pub fn from_tzid(tzid: &str) -> Option<Self> {
+        let tzid = tzid.trim().trim_matches('"');
+
+        match tzid.to_ascii_uppercase().as_str() {
+            "UTC" | "GMT" | "Z" | "ETC/UTC" | "ETC/GMT" => return Some(Self::utc()),
+            upper => {
+                if let Some(offset) = upper.strip_prefix("UTC").and_then(parse_offset) {
+                    return Some(Zone::Fixed(offset));
+                }
+            }
+        }
+
+        #[cfg(feature = "tz")]
+        {
+            let name = WINDOWS_ZONES
+                .iter()
+                .find(|(windows, _)| windows.eq_ignore_ascii_case(tzid))
+                .map_or(tzid, |(_, iana)| iana);
+            if let Ok(tz) = name.parse() {
+                return Some(Zone::Tz(tz));
+            }
+        }
+
+        None
+    }
+
+    /// `TZID` written on export (see `from_tzid`)
+    pub fn tzid(&self) -> String {
+        match self {
+            Zone::Fixed(offset) if offset.local_minus_utc() == 0 => "UTC".to_string(),
+            // no colon, which would need quoting in a parameter
+            Zone::Fixed(offset) => format!("UTC{}", offset.to_string().replace(':', "")),
+            Zone::Rules(rules) => rules.tzid().to_string(),
+            #[cfg(feature = "tz")]
+            Zone::Tz(tz) => tz.name().to_string(),
+        }
+    }
+
+    pub fn is_utc(&self) -> bool {
+        matches!(self, Zone::Fixed(offset) if offset.local_minus_utc() == 0)
+    }
+
+    /// UTC time of a local time
+    ///
+    /// When the clocks go back, the earlier time is used; times skipped when they go forward
+    /// are moved an hour later.
+    pub fn to_utc(&self, local: NaiveDateTime) -> DateTime<Utc> {
+        match self {
+            Zone::Fixed(offset) => resolve(offset, local),
+            Zone::Rules(rules) => rules.to_utc(local),
+            #[cfg(feature = "tz")]
+            Zone::Tz(tz) => resolve(tz, local),
+        }
+    }
+
+    /// Local time of a UTC time
+    pub fn local(&self, utc: DateTime<Utc>) -> NaiveDateTime {
+        match self {
+            Zone::Fixed(offset) => utc.with_timezone(offset).naive_local(),
+            Zone::Rules(rules) => utc.naive_utc() + offset_duration(rules.offset(utc.naive_utc())),
+            #[cfg(feature = "tz")]
+            Zone::Tz(tz) => utc.with_timezone(tz).naive_local(),
+        }
+    }
+
+    /// Yearly rules of the zone in `year`, as written in a `VTIMEZONE`
+    ///
+    /// IANA zones are read from their changes of the offset in `year`. A year with a single
+    /// change, or more than two, keeps the offset at its start.
+    #[cfg_attr(not(feature = "tz"), allow(unused_variables))]
+    pub fn rules(&self, year: i32) -> ZoneRules {
+        match self {
+            Zone::Fixed(offset) => ZoneRules::new(self.tzid(), *offset, None),
+            Zone::Rules(rules) => ZoneRules::clone(rules),
+            #[cfg(feature = "tz")]
+            Zone::Tz(tz) => tz_rules(tz, year),
+        }
+    }
+}
+
+impl From<FixedOffset> for Zone {
+    fn from(offset: FixedOffset) -> Self {
+        Zone::Fixed(offset)
+    }
+}
+
+impl From<ZoneRules> for Zone {
+    fn from(rules: ZoneRules) -> Self {
+        Zone::Rules(Rc::new(rules))
+    }
+}
+
+#[cfg(feature = "tz")]
+impl From<chrono_tz::Tz> for Zone {
+    fn from(tz: chrono_tz::Tz) -> Self {
+        Zone::Tz(tz)
+    }
+}
+
+/// Time zone with a standard offset and yearly daylight saving time, as described by a
+/// `VTIMEZONE`
+///
+/// Only the current rules are kept, so times before the last change of the rules may be
+/// off by the difference.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZoneRules {
+    tzid: String,
+    standard: FixedOffset,
+    daylight: Option<Daylight>,
+}
+
+/// Daylight saving time of [`ZoneRules`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Daylight {
+    pub offset: FixedOffset,
+    /// Change from the standard time, in standard time
+    pub start: Transition,
+    /// Change back to the standard time, in daylight saving time
+    pub end: Transition,
+}
+
+/// Yearly change of the offset, e.g. the second Sunday of March at 02:00
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transition {
+    pub month: u32,
+    /// Weekday with its ordinal in the month (`2SU`, `-1SU`)
+    pub day: ByDay,
+    /// Local time of the change, before it
+    pub time: NaiveTime,
+}
+
+impl Transition {
+    /// Date of the change in `year`
+    pub fn date(&self, year: i32) -> Option<NaiveDate> {
+        let first = NaiveDate::from_ymd_opt(year, self.month, 1)?;
+
+        nth_weekdays(&month_days(first), &[self.day])
+            .first()
+            .copied()
+    }
+
+    /// UTC time of the change in `year`, from a time at `offset`
+    fn utc(&self, year: i32, offset: FixedOffset) -> Option<NaiveDateTime> {
+        Some(self.date(year)?.and_time(self.time) - offset_duration(offset))
+    }
+}
+
+impl ZoneRules {
+    pub fn new(tzid: impl Into<String>, standard: FixedOffset, daylight: Option<Daylight>) -> Self {
+        Self {
+            tzid: tzid.into(),
+            standard,
+            daylight,
+        }
+    }
+
+    pub fn tzid(&self) -> &str {
+        &self.tzid
+    }
+
+    pub fn standard(&self) -> FixedOffset {
+        self.standard
+    }
+
+    pub fn daylight(&self) -> Option<&Daylight> {
+        self.daylight.as_ref()
+    }
+
+    /// Offset at a UTC time
+    fn offset(&self, utc: NaiveDateTime) -> FixedOffset {
+        let Some(daylight) = &self.daylight else {
+            return self.standard;
+        };
+        let year = utc.year();
+        let (Some(start), Some(end)) = (
+            daylight.start.utc(year, self.standard),
+            daylight.end.utc(year, daylight.offset),
+        ) else {
+            return self.standard;
+        };
+
+        // in the southern hemisphere, daylight saving time spans the new year
+        let in_daylight = if start < end {
+            start <= utc && utc < end
+        } else {
+            utc >= start || utc < end
+        };
+        if in_daylight {
+            daylight.offset
+        } else {
+            self.standard
+        }
+    }
+
+    /// Same as [`Zone::to_utc`]
+    fn to_utc(&self, local: NaiveDateTime) -> DateTime<Utc> {
+        let offsets = [Some(self.standard), self.daylight.map(|d| d.offset)];
+
+        for local in [local, local + Duration::hours(1)] {
+            let earliest = offsets
+                .iter()
+                .flatten()
+                .map(|offset| (local - offset_duration(*offset), *offset))
+                .filter(|(utc, offset)| self.offset(*utc) == *offset)
+                .map(|(utc, _)| utc)
+                .min();
+            if let Some(utc) = earliest {
+                return Utc.from_utc_datetime(&utc);
+            }
+        }
+
+        Utc.from_utc_datetime(&(local - offset_duration(self.standard)))
+    }
+}
+
+/// [`ZoneRules`] of an IANA zone, from its changes of the offset in `year`
+#[cfg(feature = "tz")]
+fn tz_rules(tz: &chrono_tz::Tz, year: i32) -> ZoneRules {
+    use chrono::Offset;
+
+    let offset = |utc: NaiveDateTime| tz.offset_from_utc_datetime(&utc).fix();
+    let Some(first) = NaiveDate::from_ymd_opt(year, 1, 1) else {
+        return ZoneRules::new(tz.name(), offset(NaiveDateTime::MIN), None);
+    };
+    let start = offset(first.and_time(NaiveTime::MIN));
+
+    // offsets change at most a few times a year, so look for them a day at a time
+    let mut changes = vec![];
+    let mut day = first.and_time(NaiveTime::MIN);
+    while day.year() == year {
+        let next = day + Duration::days(1);
+        if offset(next) != offset(day) {
+            // first minute of the day with the new offset
+            let (mut before, mut after) = (0, 24 * 60);
+            while after - before > 1 {
+                let middle = (before + after) / 2;
+                if offset(day + Duration::minutes(middle)) == offset(day) {
+                    before = middle;
+                } else {
+                    after = middle;
+                }
+            }
+            let (from, at) = (offset(day), day + Duration::minutes(after));
+            changes.push((from, offset(at), at + offset_duration(from)));
+        }
+        day = next;
+    }
+
+    let transition = |local: NaiveDateTime| {
+        let date = local.date();
+        let last = date + Duration::days(7);
+        let n = if last.month() != date.month() {
+            -1
+        } else {
+            (date.day() as i32 - 1) / 7 + 1
+        };
+        Transition {
+            month: date.month(),
+            day: ByDay {
+                n: Some(n),
+                weekday: date.weekday(),
+            },
+            time: local.time(),
+        }
+    };
+    let daylight = match changes[..] {
+        [(from, to, at), (_, back, until)] if from == back => {
+            let (standard, daylight, start, end) = if from.local_minus_utc() < to.local_minus_utc()
+            {
+                (from, to, at, until)
+            } else {
+                (to, from, until, at)
+            };
+            Some((
+                standard,
+                Daylight {
+                    offset: daylight,
+                    start: transition(start),
+                    end: transition(end),
+                },
+            ))
+        }
+        _ => None,
+    };
+
+    match daylight {
+        Some((standard, daylight)) => ZoneRules::new(tz.name(), standard, Some(daylight)),
+        None => ZoneRules::new(tz.name(), start, None),
+    }
+}
+
+fn offset_duration(offset: FixedOffset) -> Duration {
+    Duration::seconds(offset.local_minus_utc() as i64)
+}
+
+fn resolve<Z: TimeZone>(zone: &Z, local: NaiveDateTime) -> DateTime<Utc> {
+    zone.from_local_datetime(&local)
+        .earliest()
+        .or_else(|| {
+            zone.from_local_datetime(&(local + Duration::hours(1)))
+                .earliest()
+        })
+        .map(|t| t.with_timezone(&Utc))
+        .unwrap_or_else(|| Utc.from_utc_datetime(&local))
+}
+
+/// `+09:00`, `-0530`, `+9`
+pub(crate) fn parse_offset(s: &str) -> Option<FixedOffset> {
+    let (sign, rest) = match s.as_bytes().first()? {
+        b'+' => (1, &s[1..]),
+        b'-' => (-1, &s[1..]),
+        _ => return None,
+    };
+    let digits: String = rest.chars().filter(|c| *c != ':').collect();
+    if digits.is_empty() || digits.len() > 4 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let (hours, minutes) = if digits.len() > 2 {
+        digits.split_at(digits.len() - 2)
+    } else {
+        (digits.as_str(), "0")
+    };
+    let seconds = hours.parse::<i32>().ok()? * 3600 + minutes.parse::<i32>().ok()? * 60;
+
+    FixedOffset::east_opt(sign * seconds)
+}
+
+/// Windows time zone names (as written by Outlook and Exchange) and their IANA zones,
+/// from the territory-neutral entries of CLDR's `windowsZones.xml`
+#[cfg(feature = "tz")]
+const WINDOWS_ZONES: &[(&str, &str)] = &[
+    ("Dateline Standard Time", "Etc/GMT+12"),
+    ("UTC-11", "Etc/GMT+11"),
+    ("Hawaiian Standard Time", "Pacific/Honolulu"),
+    ("Alaskan Standard Time", "America/Anchorage"),
+    ("Pacific Standard Time", "America/Los_Angeles"),
+    ("US Mountain Standard Time", "America/Phoenix"),
+    ("Mountain Standard Time", "America/Denver"),
+    ("Central America Standard Time", "America/Guatemala"),
+    ("Central Standard Time", "America/Chicago"),
+    ("Central Standard Time (Mexico)", "America/Mexico_City"),
+    ("Canada Central Standard Time", "America/Regina"),
+    ("SA Pacific Standard Time", "America/Bogota"),
+    ("Eastern Standard Time", "America/New_York"),
+    ("US Eastern Standard Time", "America/Indiana/Indianapolis"),
+    ("Atlantic Standard Time", "America/Halifax"),
+    ("Newfoundland Standard Time", "America/St_Johns"),
+    ("E. South America Standard Time", "America/Sao_Paulo"),
+    ("Argentina Standard Time", "America/Argentina/Buenos_Aires"),
+    ("GMT Standard Time", "Europe/London"),
+    ("Greenwich Standard Time", "Atlantic/Reykjavik"),
+    ("W. Europe Standard Time", "Europe/Berlin"),
+    ("Central Europe Standard Time", "Europe/Budapest"),
+    ("Romance Standard Time", "Europe/Paris"),
+    ("Central European Standard Time", "Europe/Warsaw"),
+    ("W. Central Africa Standard Time", "Africa/Lagos"),
+    ("GTB Standard Time", "Europe/Bucharest"),
+    ("FLE Standard Time", "Europe/Kiev"),
+    ("Israel Standard Time", "Asia/Jerusalem"),
+    ("Egypt Standard Time", "Africa/Cairo"),
+    ("South Africa Standard Time", "Africa/Johannesburg"),
+    ("Russian Standard Time", "Europe/Moscow"),
+    ("Turkey Standard Time", "Europe/Istanbul"),
+    ("Arab Standard Time", "Asia/Riyadh"),
+    ("Arabian Standard Time", "Asia/Dubai"),
+    ("Iran Standard Time", "Asia/Tehran"),
+    ("Pakistan Standard Time", "Asia/Karachi"),
+    ("India Standard Time", "Asia/Kolkata"),
+    ("Nepal Standard Time", "Asia/Kathmandu"),
+    ("Bangladesh Standard Time", "Asia/Dhaka"),
+    ("SE Asia Standard Time", "Asia/Bangkok"),
+    ("China Standard Time", "Asia/Shanghai"),
+    ("Singapore Standard Time", "Asia/Singapore"),
+    ("Taipei Standard Time", "Asia/Taipei"),
+    ("W. Australia Standard Time", "Australia/Perth"),
+    ("Tokyo Standard Time", "Asia/Tokyo"),
+    ("Korea Standard Time", "Asia/Seoul"),
+    ("Cen. Australia Standard Time", "Australia/Adelaide"),
+    ("AUS Central Standard Time", "Australia/Darwin"),
+    ("E. Australia Standard Time", "Australia/Brisbane"),
+    ("AUS Eastern Standard Time", "Australia/Sydney"),
+    ("Tasmania Standard Time", "Australia/Hobart"),
+    ("New Zealand Standard Time", "Pacific/Auckland"),
+];
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, Timelike, Weekday};
+
+    use super::*;
+
+    #[test]
+    fn test() {
+        let tokyo = Zone::from_tzid("UTC+09:00").unwrap();
+        assert_eq!(tokyo.tzid(), "UTC+0900");
+        assert_eq!(Zone::from_tzid(&tokyo.tzid()), Some(tokyo.clone()));
+        assert_eq!(Zone::from_tzid("Etc/UTC"), Some(Zone::utc()));
+        assert_eq!(Zone::from_tzid("UTC-5:30").unwrap().tzid(), "UTC-0530");
+
+        let local = NaiveDate::from_ymd_opt(2024, 6, 5)
+            .unwrap()
+            .and_hms_opt(8, 0, 0)
+            .unwrap();
+        let utc = tokyo.to_utc(local);
+        assert_eq!(utc.hour(), 23);
+        assert_eq!(tokyo.local(utc), local);
+
+        #[cfg(not(feature = "tz"))]
+        assert_eq!(Zone::from_tzid("Asia/Tokyo"), None);
+        #[cfg(feature = "tz")]
+        {
+            assert_eq!(
+                Zone::from_tzid("America/New_York").unwrap().tzid(),
+                "America/New_York"
+            );
+            assert_eq!(
+                Zone::from_tzid("Tokyo Standard Time").unwrap().tzid(),
+                "Asia/Tokyo"
+            );
+        }
+    }
+
+    #[test]
+    fn test_rules() {
+        let hours = |h| FixedOffset::east_opt(h * 3600).unwrap();
+        let at = |d: &str, h| {
+            NaiveDate::parse_from_str(d, "%Y-%m-%d")
+                .unwrap()
+                .and_hms_opt(h, 0, 0)
+                .unwrap()
+        };
+        let transition = |month, n, time| Transition {
+            month,
+            day: ByDay {
+                n: Some(n),
+                weekday: Weekday::Sun,
+            },
+            time: NaiveTime::from_hms_opt(time, 0, 0).unwrap(),
+        };
+        let new_york = Zone::from(ZoneRules::new(
+            "America/New_York",
+            hours(-5),
+            Some(Daylight {
+                offset: hours(-4),
+                start: transition(3, 2, 2),
+                end: transition(11, 1, 2),
+            }),
+        ));
+
+        assert_eq!(new_york.to_utc(at("2024-01-15", 9)).hour(), 14);
+        assert_eq!(new_york.to_utc(at("2024-07-15", 9)).hour(), 13);
+        // 02:30 on March 10 does not exist, 01:30 on November 3 happens twice
+        assert_eq!(new_york.to_utc(at("2024-03-10", 2)).hour(), 7);
+        assert_eq!(new_york.to_utc(at("2024-11-03", 1)).hour(), 5);
+        let utc = new_york.to_utc(at("2024-11-03", 12));
+        assert_eq!(new_york.local(utc), at("2024-11-03", 12));
+
+        // daylight saving time over the new year
+        let sydney = Zone::from(ZoneRules::new(
+            "Australia/Sydney",
+            hours(10),
+            Some(Daylight {
+                offset: hours(11),
+                start: transition(10, 1, 2),
+                end: transition(4, 1, 3),
+            }),
+        ));
+        assert_eq!(sydney.to_utc(at("2024-01-15", 12)).hour(), 1);
+        assert_eq!(sydney.to_utc(at("2024-06-15", 12)).hour(), 2);
+        assert_eq!(sydney.tzid(), "Australia/Sydney");
+    }
+
+    #[cfg(feature = "tz")]
+    #[test]
+    fn test_tz_rules() {
+        let hours = |h| FixedOffset::east_opt(h * 3600).unwrap();
+        let sunday = |n| ByDay {
+            n: Some(n),
+            weekday: Weekday::Sun,
+        };
+        let two = NaiveTime::from_hms_opt(2, 0, 0).unwrap();
+
+        let new_york = Zone::from_tzid("America/New_York").unwrap().rules(2024);
+        assert_eq!(new_york.standard(), hours(-5));
+        let daylight = new_york.daylight().unwrap();
+        assert_eq!(daylight.offset, hours(-4));
+        assert_eq!((daylight.start.month, daylight.start.day), (3, sunday(2)));
+        assert_eq!((daylight.end.month, daylight.end.day), (11, sunday(1)));
+        assert_eq!((daylight.start.time, daylight.end.time), (two, two));
+
+        let sydney = Zone::from_tzid("Australia/Sydney").unwrap().rules(2024);
+        assert_eq!(sydney.standard(), hours(10));
+        let daylight = sydney.daylight().unwrap();
+        assert_eq!((daylight.start.month, daylight.end.month), (10, 4));
+        assert_eq!(daylight.end.time, NaiveTime::from_hms_opt(3, 0, 0).unwrap());
+
+        let tokyo = Zone::from_tzid("Asia/Tokyo").unwrap().rules(2024);
+        assert_eq!((tokyo.standard(), tokyo.daylight()), (hours(9), None));
+    }
+}